@@ -99,3 +99,61 @@ where
         }
     })
 }
+
+// Vectorized slice operations compared against iterating over each `ColorRGB`.
+
+#[bench]
+fn bench_blend_large_slice(b: &mut Bencher) {
+    let f = |slice: &mut [ColorRGB], amt: u8| slice.blend(ColorRGB::Teal, amt);
+    bench_fade_large(b, f);
+}
+
+#[bench]
+fn bench_blend_large_iter(b: &mut Bencher) {
+    let f = |slice: &mut [ColorRGB], amt: u8| {
+        slice.iter_mut().for_each(|p| p.blend(ColorRGB::Teal, amt))
+    };
+    bench_fade_large(b, f);
+}
+
+#[bench]
+fn bench_blur_large_slice(b: &mut Bencher) {
+    let f = |slice: &mut [ColorRGB], amt: u8| slice.blur(amt);
+    bench_fade_large(b, f);
+}
+
+#[bench]
+fn bench_blur_large_iter(b: &mut Bencher) {
+    let f = |slice: &mut [ColorRGB], amt: u8| blur_iter(slice, amt);
+    bench_fade_large(b, f);
+}
+
+#[bench]
+fn bench_fill_large_slice(b: &mut Bencher) {
+    let f = |slice: &mut [ColorRGB], amt: u8| slice.fill_solid(ColorRGB::new(amt, 0, amt));
+    bench_fade_large(b, f);
+}
+
+#[bench]
+fn bench_fill_large_iter(b: &mut Bencher) {
+    let f = |slice: &mut [ColorRGB], amt: u8| slice.iter_mut().fill(ColorRGB::new(amt, 0, amt));
+    bench_fade_large(b, f);
+}
+
+/// The same blur as `ColorSliceMut::blur`, one pixel at a time.
+fn blur_iter(slice: &mut [ColorRGB], blur_amount: u8) {
+    let keep: u8 = 255 - blur_amount;
+    let seep: u8 = blur_amount >> 1;
+    let mut carry: ColorRGB = ColorRGB::Black;
+    let mut iter = slice.iter_mut().peekable();
+    while let Some(cur) = iter.next() {
+        cur.scale(keep);
+        *cur += carry;
+        if let Some(nxt) = iter.peek() {
+            let mut part: ColorRGB = **nxt;
+            part.scale(seep);
+            *cur += part;
+            carry = part;
+        }
+    }
+}
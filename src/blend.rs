@@ -0,0 +1,229 @@
+//! Blend modes for combining one layer of color on top of another.
+
+use crate::ColorRGB;
+
+/// Methods of combining a top color (the "layer") with a base color beneath it.
+///
+/// Each mode is applied to every component independently. In the formulas below, `a` is the
+/// base component, `b` is the top component, and `mul(x, y)` is the product of two components
+/// divided by 255, rounded to the nearest integer. This makes `mul(x, 255) == x` and
+/// `mul(x, 0) == 0` exact.
+///
+/// | Mode         | Formula                                                             |
+/// |--------------|---------------------------------------------------------------------|
+/// | `Normal`     | `b`                                                                 |
+/// | `Add`        | `min(a + b, 255)`                                                   |
+/// | `Subtract`   | `max(a - b, 0)`                                                     |
+/// | `Multiply`   | `mul(a, b)`                                                         |
+/// | `Screen`     | `255 - mul(255 - a, 255 - b)`                                       |
+/// | `Overlay`    | `mul(2a, b)` if `a < 128`, otherwise `255 - mul(2(255 - a), 255 - b)` |
+/// | `Lighten`    | `max(a, b)`                                                         |
+/// | `Darken`     | `min(a, b)`                                                         |
+/// | `Difference` | `abs(a - b)`                                                        |
+/// | `SoftLight`  | `a * (255a + 2b(255 - a)) / 65025`, rounded to the nearest integer  |
+///
+/// `SoftLight` is the "pegtop" soft light, which unlike the Photoshop formula is continuous
+/// and needs no square root.
+///
+/// Once combined, the result is blended with the base color by an `opacity`, using the same
+/// rules as `ColorRGB::blend()`. An opacity of `255` gives exactly the combined color, and an
+/// opacity of `0` leaves the base untouched.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::{BlendMode, ColorRGB};
+///
+/// let base = ColorRGB::new(200, 100, 0);
+/// let top = ColorRGB::new(128, 128, 128);
+///
+/// assert_eq!(BlendMode::Multiply.apply(base, top), ColorRGB::new(100, 50, 0));
+/// assert_eq!(BlendMode::Lighten.apply(base, top), ColorRGB::new(200, 128, 128));
+/// assert_eq!(BlendMode::Multiply.blend(base, top, 0), base);
+/// ```
+///
+/// Blending a whole layer onto a slice:
+///
+/// ```
+/// use cichlid::{prelude::*, BlendMode, ColorRGB};
+///
+/// let mut base = [ColorRGB::new(10, 20, 30); 16];
+/// let layer = [ColorRGB::new(5, 5, 5); 16];
+///
+/// base.blend_with(&layer, BlendMode::Subtract, 255);
+/// assert!(base.iter().all(|c| *c == ColorRGB::new(5, 15, 25)));
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub enum BlendMode {
+    /// The top color replaces the base color.
+    #[default]
+    Normal,
+    /// Adds the colors together, saturating at full brightness.
+    Add,
+    /// Subtracts the top color from the base color, saturating at black.
+    Subtract,
+    /// Multiplies the colors together, which darkens the base.
+    Multiply,
+    /// The inverse of multiplying the inverted colors, which lightens the base.
+    Screen,
+    /// Multiplies dark base colors and screens light base colors.
+    Overlay,
+    /// Takes the brighter of each component.
+    Lighten,
+    /// Takes the darker of each component.
+    Darken,
+    /// The absolute difference of each component.
+    Difference,
+    /// A gentler version of `Overlay`.
+    SoftLight,
+}
+
+impl BlendMode {
+    /// Combines the `top` color with the `base` color at full opacity.
+    #[inline]
+    pub fn apply(self, base: ColorRGB, top: ColorRGB) -> ColorRGB {
+        ColorRGB::new(
+            self.apply_u8(base.r, top.r),
+            self.apply_u8(base.g, top.g),
+            self.apply_u8(base.b, top.b),
+        )
+    }
+
+    /// Combines the `top` color with the `base` color, then blends the result with `base`
+    /// by `opacity`.
+    #[inline]
+    pub fn blend(self, base: ColorRGB, top: ColorRGB, opacity: u8) -> ColorRGB {
+        let mut out = base;
+        if opacity != 0 {
+            out.blend(self.apply(base, top), opacity);
+        }
+        out
+    }
+
+    /// Combines a single component `b` on top of the component `a`.
+    #[inline]
+    pub fn apply_u8(self, a: u8, b: u8) -> u8 {
+        match self {
+            BlendMode::Normal => b,
+            BlendMode::Add => a.saturating_add(b),
+            BlendMode::Subtract => a.saturating_sub(b),
+            BlendMode::Multiply => mul(a, b),
+            BlendMode::Screen => screen(a, b),
+            BlendMode::Overlay => {
+                if a < 128 {
+                    mul(a << 1, b)
+                } else {
+                    255 - mul((255 - a) << 1, 255 - b)
+                }
+            }
+            BlendMode::Lighten => a.max(b),
+            BlendMode::Darken => a.min(b),
+            BlendMode::Difference => a.max(b) - a.min(b),
+            BlendMode::SoftLight => soft_light(a, b),
+        }
+    }
+}
+
+/// `x * y / 255`, rounded to the nearest integer.
+#[inline(always)]
+pub(crate) const fn mul(x: u8, y: u8) -> u8 {
+    let t: u16 = x as u16 * y as u16 + 128;
+    ((t + (t >> 8)) >> 8) as u8
+}
+
+#[inline(always)]
+const fn screen(a: u8, b: u8) -> u8 {
+    255 - mul(255 - a, 255 - b)
+}
+
+/// Pegtop soft light, `(1 - 2b)a^2 + 2ab` when normalized to the range `[0, 1]`.
+#[inline(always)]
+const fn soft_light(a: u8, b: u8) -> u8 {
+    let a = a as u32;
+    let b = b as u32;
+    ((a * (255 * a + 2 * b * (255 - a)) + 32512) / 65025) as u8
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MODES: [BlendMode; 10] = [
+        BlendMode::Normal,
+        BlendMode::Add,
+        BlendMode::Subtract,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Overlay,
+        BlendMode::Lighten,
+        BlendMode::Darken,
+        BlendMode::Difference,
+        BlendMode::SoftLight,
+    ];
+
+    #[test]
+    fn mul_is_rounded_division() {
+        for x in 0..=255u16 {
+            for y in 0..=255u16 {
+                let expected = (x * y + 127) / 255;
+                assert_eq!(u16::from(mul(x as u8, y as u8)), expected, "{} * {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn modes_match_reference() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                let (fa, fb) = (f64::from(a) / 255.0, f64::from(b) / 255.0);
+                let soft = (1.0 - 2.0 * fb) * fa * fa + 2.0 * fb * fa;
+                let overlay = if a < 128 {
+                    2.0 * f64::from(a) * fb / 255.0
+                } else {
+                    1.0 - 2.0 * f64::from(255 - a) * (1.0 - fb) / 255.0
+                };
+                let expected = [
+                    (BlendMode::Multiply, fa * fb),
+                    (BlendMode::Screen, 1.0 - (1.0 - fa) * (1.0 - fb)),
+                    (BlendMode::Overlay, overlay),
+                    (BlendMode::SoftLight, soft),
+                ];
+                for (mode, value) in expected.iter() {
+                    let actual = f64::from(mode.apply_u8(a, b));
+                    assert!(
+                        (actual - value * 255.0).abs() <= 0.5 + 1e-9,
+                        "{:?}: a: {}, b: {}, got {}",
+                        mode,
+                        a,
+                        b,
+                        actual
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn identities() {
+        for a in 0..=255u8 {
+            assert_eq!(BlendMode::Multiply.apply_u8(a, 255), a);
+            assert_eq!(BlendMode::Multiply.apply_u8(a, 0), 0);
+            assert_eq!(BlendMode::Screen.apply_u8(a, 0), a);
+            assert_eq!(BlendMode::Screen.apply_u8(a, 255), 255);
+            assert_eq!(BlendMode::Difference.apply_u8(a, a), 0);
+            assert_eq!(BlendMode::SoftLight.apply_u8(0, a), 0);
+            assert_eq!(BlendMode::SoftLight.apply_u8(255, a), 255);
+        }
+    }
+
+    #[test]
+    fn opacity_extremes() {
+        let base = ColorRGB::new(13, 200, 99);
+        let top = ColorRGB::new(250, 4, 128);
+        for mode in MODES.iter() {
+            assert_eq!(mode.blend(base, top, 0), base);
+            assert_eq!(mode.blend(base, top, 255), mode.apply(base, top));
+        }
+        assert_eq!(BlendMode::Normal.blend(base, top, 255), top);
+    }
+}
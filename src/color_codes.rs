@@ -3,6 +3,8 @@
 #![allow(non_upper_case_globals)]
 #![allow(clippy::unreadable_literal)]
 
+use crate::ColorRGB;
+
 pub const AliceBlue: u32 = 0xF0F8FF;
 pub const Amethyst: u32 = 0x9966CC;
 pub const AntiqueWhite: u32 = 0xFAEBD7;
@@ -151,3 +153,165 @@ pub const White: u32 = 0xFFFFFF;
 pub const WhiteSmoke: u32 = 0xF5F5F5;
 pub const Yellow: u32 = 0xFFFF00;
 pub const YellowGreen: u32 = 0x9ACD32;
+
+/// Every color code above as a `ColorRGB`, along with its name.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::color_codes::NAMED_COLORS;
+///
+/// let (name, color) = NAMED_COLORS[0];
+/// assert_eq!(name, "AliceBlue");
+/// assert_eq!(color, cichlid::ColorRGB::AliceBlue);
+/// ```
+pub const NAMED_COLORS: [(&str, ColorRGB); 148] = [
+    ("AliceBlue", ColorRGB::from_color_code(AliceBlue)),
+    ("Amethyst", ColorRGB::from_color_code(Amethyst)),
+    ("AntiqueWhite", ColorRGB::from_color_code(AntiqueWhite)),
+    ("Aqua", ColorRGB::from_color_code(Aqua)),
+    ("Aquamarine", ColorRGB::from_color_code(Aquamarine)),
+    ("Azure", ColorRGB::from_color_code(Azure)),
+    ("Beige", ColorRGB::from_color_code(Beige)),
+    ("Bisque", ColorRGB::from_color_code(Bisque)),
+    ("Black", ColorRGB::from_color_code(Black)),
+    ("BlanchedAlmond", ColorRGB::from_color_code(BlanchedAlmond)),
+    ("Blue", ColorRGB::from_color_code(Blue)),
+    ("BlueViolet", ColorRGB::from_color_code(BlueViolet)),
+    ("Brown", ColorRGB::from_color_code(Brown)),
+    ("BurlyWood", ColorRGB::from_color_code(BurlyWood)),
+    ("CadetBlue", ColorRGB::from_color_code(CadetBlue)),
+    ("Chartreuse", ColorRGB::from_color_code(Chartreuse)),
+    ("Chocolate", ColorRGB::from_color_code(Chocolate)),
+    ("Coral", ColorRGB::from_color_code(Coral)),
+    ("CornflowerBlue", ColorRGB::from_color_code(CornflowerBlue)),
+    ("Cornsilk", ColorRGB::from_color_code(Cornsilk)),
+    ("Crimson", ColorRGB::from_color_code(Crimson)),
+    ("Cyan", ColorRGB::from_color_code(Cyan)),
+    ("DarkBlue", ColorRGB::from_color_code(DarkBlue)),
+    ("DarkCyan", ColorRGB::from_color_code(DarkCyan)),
+    ("DarkGoldenrod", ColorRGB::from_color_code(DarkGoldenrod)),
+    ("DarkGray", ColorRGB::from_color_code(DarkGray)),
+    ("DarkGrey", ColorRGB::from_color_code(DarkGrey)),
+    ("DarkGreen", ColorRGB::from_color_code(DarkGreen)),
+    ("DarkKhaki", ColorRGB::from_color_code(DarkKhaki)),
+    ("DarkMagenta", ColorRGB::from_color_code(DarkMagenta)),
+    ("DarkOliveGreen", ColorRGB::from_color_code(DarkOliveGreen)),
+    ("DarkOrange", ColorRGB::from_color_code(DarkOrange)),
+    ("DarkOrchid", ColorRGB::from_color_code(DarkOrchid)),
+    ("DarkRed", ColorRGB::from_color_code(DarkRed)),
+    ("DarkSalmon", ColorRGB::from_color_code(DarkSalmon)),
+    ("DarkSeaGreen", ColorRGB::from_color_code(DarkSeaGreen)),
+    ("DarkSlateBlue", ColorRGB::from_color_code(DarkSlateBlue)),
+    ("DarkSlateGray", ColorRGB::from_color_code(DarkSlateGray)),
+    ("DarkSlateGrey", ColorRGB::from_color_code(DarkSlateGrey)),
+    ("DarkTurquoise", ColorRGB::from_color_code(DarkTurquoise)),
+    ("DarkViolet", ColorRGB::from_color_code(DarkViolet)),
+    ("DeepPink", ColorRGB::from_color_code(DeepPink)),
+    ("DeepSkyBlue", ColorRGB::from_color_code(DeepSkyBlue)),
+    ("DimGray", ColorRGB::from_color_code(DimGray)),
+    ("DimGrey", ColorRGB::from_color_code(DimGrey)),
+    ("DodgerBlue", ColorRGB::from_color_code(DodgerBlue)),
+    ("FireBrick", ColorRGB::from_color_code(FireBrick)),
+    ("FloralWhite", ColorRGB::from_color_code(FloralWhite)),
+    ("ForestGreen", ColorRGB::from_color_code(ForestGreen)),
+    ("Fuchsia", ColorRGB::from_color_code(Fuchsia)),
+    ("Gainsboro", ColorRGB::from_color_code(Gainsboro)),
+    ("GhostWhite", ColorRGB::from_color_code(GhostWhite)),
+    ("Gold", ColorRGB::from_color_code(Gold)),
+    ("Goldenrod", ColorRGB::from_color_code(Goldenrod)),
+    ("Gray", ColorRGB::from_color_code(Gray)),
+    ("Grey", ColorRGB::from_color_code(Grey)),
+    ("Green", ColorRGB::from_color_code(Green)),
+    ("GreenYellow", ColorRGB::from_color_code(GreenYellow)),
+    ("Honeydew", ColorRGB::from_color_code(Honeydew)),
+    ("HotPink", ColorRGB::from_color_code(HotPink)),
+    ("IndianRed", ColorRGB::from_color_code(IndianRed)),
+    ("Indigo", ColorRGB::from_color_code(Indigo)),
+    ("Ivory", ColorRGB::from_color_code(Ivory)),
+    ("Khaki", ColorRGB::from_color_code(Khaki)),
+    ("Lavender", ColorRGB::from_color_code(Lavender)),
+    ("LavenderBlush", ColorRGB::from_color_code(LavenderBlush)),
+    ("LawnGreen", ColorRGB::from_color_code(LawnGreen)),
+    ("LemonChiffon", ColorRGB::from_color_code(LemonChiffon)),
+    ("LightBlue", ColorRGB::from_color_code(LightBlue)),
+    ("LightCoral", ColorRGB::from_color_code(LightCoral)),
+    ("LightCyan", ColorRGB::from_color_code(LightCyan)),
+    ("LightGoldenrodYellow", ColorRGB::from_color_code(LightGoldenrodYellow)),
+    ("LightGreen", ColorRGB::from_color_code(LightGreen)),
+    ("LightGrey", ColorRGB::from_color_code(LightGrey)),
+    ("LightPink", ColorRGB::from_color_code(LightPink)),
+    ("LightSalmon", ColorRGB::from_color_code(LightSalmon)),
+    ("LightSeaGreen", ColorRGB::from_color_code(LightSeaGreen)),
+    ("LightSkyBlue", ColorRGB::from_color_code(LightSkyBlue)),
+    ("LightSlateGray", ColorRGB::from_color_code(LightSlateGray)),
+    ("LightSlateGrey", ColorRGB::from_color_code(LightSlateGrey)),
+    ("LightSteelBlue", ColorRGB::from_color_code(LightSteelBlue)),
+    ("LightYellow", ColorRGB::from_color_code(LightYellow)),
+    ("Lime", ColorRGB::from_color_code(Lime)),
+    ("LimeGreen", ColorRGB::from_color_code(LimeGreen)),
+    ("Linen", ColorRGB::from_color_code(Linen)),
+    ("Magenta", ColorRGB::from_color_code(Magenta)),
+    ("Maroon", ColorRGB::from_color_code(Maroon)),
+    ("MediumAquamarine", ColorRGB::from_color_code(MediumAquamarine)),
+    ("MediumBlue", ColorRGB::from_color_code(MediumBlue)),
+    ("MediumOrchid", ColorRGB::from_color_code(MediumOrchid)),
+    ("MediumPurple", ColorRGB::from_color_code(MediumPurple)),
+    ("MediumSeaGreen", ColorRGB::from_color_code(MediumSeaGreen)),
+    ("MediumSlateBlue", ColorRGB::from_color_code(MediumSlateBlue)),
+    ("MediumSpringGreen", ColorRGB::from_color_code(MediumSpringGreen)),
+    ("MediumTurquoise", ColorRGB::from_color_code(MediumTurquoise)),
+    ("MediumVioletRed", ColorRGB::from_color_code(MediumVioletRed)),
+    ("MidnightBlue", ColorRGB::from_color_code(MidnightBlue)),
+    ("MintCream", ColorRGB::from_color_code(MintCream)),
+    ("MistyRose", ColorRGB::from_color_code(MistyRose)),
+    ("Moccasin", ColorRGB::from_color_code(Moccasin)),
+    ("NavajoWhite", ColorRGB::from_color_code(NavajoWhite)),
+    ("Navy", ColorRGB::from_color_code(Navy)),
+    ("OldLace", ColorRGB::from_color_code(OldLace)),
+    ("Olive", ColorRGB::from_color_code(Olive)),
+    ("OliveDrab", ColorRGB::from_color_code(OliveDrab)),
+    ("Orange", ColorRGB::from_color_code(Orange)),
+    ("OrangeRed", ColorRGB::from_color_code(OrangeRed)),
+    ("Orchid", ColorRGB::from_color_code(Orchid)),
+    ("PaleGoldenrod", ColorRGB::from_color_code(PaleGoldenrod)),
+    ("PaleGreen", ColorRGB::from_color_code(PaleGreen)),
+    ("PaleTurquoise", ColorRGB::from_color_code(PaleTurquoise)),
+    ("PaleVioletRed", ColorRGB::from_color_code(PaleVioletRed)),
+    ("PapayaWhip", ColorRGB::from_color_code(PapayaWhip)),
+    ("PeachPuff", ColorRGB::from_color_code(PeachPuff)),
+    ("Peru", ColorRGB::from_color_code(Peru)),
+    ("Pink", ColorRGB::from_color_code(Pink)),
+    ("Plaid", ColorRGB::from_color_code(Plaid)),
+    ("Plum", ColorRGB::from_color_code(Plum)),
+    ("PowderBlue", ColorRGB::from_color_code(PowderBlue)),
+    ("Purple", ColorRGB::from_color_code(Purple)),
+    ("Red", ColorRGB::from_color_code(Red)),
+    ("RosyBrown", ColorRGB::from_color_code(RosyBrown)),
+    ("RoyalBlue", ColorRGB::from_color_code(RoyalBlue)),
+    ("SaddleBrown", ColorRGB::from_color_code(SaddleBrown)),
+    ("Salmon", ColorRGB::from_color_code(Salmon)),
+    ("SandyBrown", ColorRGB::from_color_code(SandyBrown)),
+    ("SeaGreen", ColorRGB::from_color_code(SeaGreen)),
+    ("Seashell", ColorRGB::from_color_code(Seashell)),
+    ("Sienna", ColorRGB::from_color_code(Sienna)),
+    ("Silver", ColorRGB::from_color_code(Silver)),
+    ("SkyBlue", ColorRGB::from_color_code(SkyBlue)),
+    ("SlateBlue", ColorRGB::from_color_code(SlateBlue)),
+    ("SlateGray", ColorRGB::from_color_code(SlateGray)),
+    ("SlateGrey", ColorRGB::from_color_code(SlateGrey)),
+    ("Snow", ColorRGB::from_color_code(Snow)),
+    ("SpringGreen", ColorRGB::from_color_code(SpringGreen)),
+    ("SteelBlue", ColorRGB::from_color_code(SteelBlue)),
+    ("Tan", ColorRGB::from_color_code(Tan)),
+    ("Teal", ColorRGB::from_color_code(Teal)),
+    ("Thistle", ColorRGB::from_color_code(Thistle)),
+    ("Tomato", ColorRGB::from_color_code(Tomato)),
+    ("Turquoise", ColorRGB::from_color_code(Turquoise)),
+    ("Violet", ColorRGB::from_color_code(Violet)),
+    ("Wheat", ColorRGB::from_color_code(Wheat)),
+    ("White", ColorRGB::from_color_code(White)),
+    ("WhiteSmoke", ColorRGB::from_color_code(WhiteSmoke)),
+    ("Yellow", ColorRGB::from_color_code(Yellow)),
+    ("YellowGreen", ColorRGB::from_color_code(YellowGreen)),
+];
@@ -5,7 +5,9 @@ use core::slice;
 #[cfg(not(feature = "no-std"))]
 use std::slice;
 
-use crate::ColorRGB;
+use crate::color_util::simd;
+use crate::math::Q8_8;
+use crate::{BlendMode, ColorRGB};
 
 // Developer note:
 //
@@ -52,18 +54,16 @@ impl<'a> super::ColorSliceMut for &'a mut [ColorRGB] {
     fn blur(self, blur_amount: u8) {
         let keep: u8 = 255 - blur_amount;
         let seep: u8 = blur_amount >> 1;
-        let mut carry: ColorRGB = ColorRGB::Black;
-        let mut iter = self.into_iter().peekable();
-        while let Some(cur) = iter.next() {
-            cur.scale(keep);
-            *cur += carry;
-            if let Some(nxt) = iter.peek() {
-                let mut part: ColorRGB = **nxt;
+        let done: usize = simd::blur(self, keep, seep);
+        let carry: ColorRGB = match done {
+            0 => ColorRGB::Black,
+            _ => {
+                let mut part: ColorRGB = self[done];
                 part.scale(seep);
-                *cur += part;
-                carry = part;
+                part
             }
-        }
+        };
+        blur_scalar(&mut self[done..], keep, seep, carry);
     }
 
     fn fade_to_black(self, fade_by: u8) {
@@ -76,36 +76,171 @@ impl<'a> super::ColorSliceMut for &'a mut [ColorRGB] {
     }
 
     fn blend(self, other: ColorRGB, amount_of_other: u8) {
-        let p_other: u16 = amount_of_other as u16;
-        let p_this: u16 = (255 - amount_of_other) as u16;
+        let done: usize = simd::blend(self, other, amount_of_other);
+        blend_scalar(&mut self[done..], other, amount_of_other);
+    }
 
-        let partial_r = other.r as u16 * p_other;
-        let partial_g = other.g as u16 * p_other;
-        let partial_b = other.b as u16 * p_other;
+    fn fill_solid(self, color: ColorRGB) {
+        let done: usize = simd::fill(self, color);
+        self[done..].iter_mut().for_each(|p| *p = color);
+    }
 
-        self.iter_mut().for_each(|p| {
-            p.r = (((p.r as u16 * p_this) + partial_r) >> 8) as u8;
-            p.g = (((p.g as u16 * p_this) + partial_g) >> 8) as u8;
-            p.b = (((p.b as u16 * p_this) + partial_b) >> 8) as u8;
-        });
+    fn hue_rotate(self, amount: u8) {
+        self.iter_mut().for_each(|p| p.hue_rotate(amount));
+    }
+
+    fn invert_hue(self) {
+        self.iter_mut().for_each(|p| p.invert_hue());
+    }
+
+    fn saturate(self, amount: u8) {
+        self.iter_mut().for_each(|p| p.saturate(amount));
+    }
+
+    fn desaturate(self, amount: u8) {
+        self.iter_mut().for_each(|p| p.desaturate(amount));
+    }
+
+    fn grayscale(self) {
+        self.iter_mut().for_each(|p| p.grayscale());
+    }
+
+    fn blend_with(self, layer: &[ColorRGB], mode: BlendMode, opacity: u8) {
+        if opacity == 0 {
+            return;
+        }
+        self.iter_mut()
+            .zip(layer.iter())
+            .for_each(|(p, top)| *p = mode.blend(*p, *top, opacity));
+    }
+
+    fn rotate_by(self, amount: isize) {
+        if !self.is_empty() {
+            let len: isize = self.len() as isize;
+            self.rotate_right(amount.rem_euclid(len) as usize);
+        }
+    }
+
+    fn shift_by(self, amount: isize, fill: ColorRGB) {
+        let len: usize = self.len();
+        let n: usize = amount.unsigned_abs().min(len);
+        if amount >= 0 {
+            self.copy_within(..len - n, n);
+            self[..n].iter_mut().for_each(|p| *p = fill);
+        } else {
+            self.copy_within(n.., 0);
+            self[len - n..].iter_mut().for_each(|p| *p = fill);
+        }
     }
+
+    fn shift_fract(self, amount: Q8_8, fill: ColorRGB) {
+        let whole: isize = isize::from(amount.whole());
+        let fract: u8 = amount.fract();
+        if fract == 0 {
+            return self.shift_by(whole, fill);
+        }
+        // Each pixel is blended from the pixels `whole` and `whole + 1` before it.
+        let len: isize = self.len() as isize;
+        let source = |pixels: &[ColorRGB], i: isize| match i {
+            i if i >= 0 && i < len => pixels[i as usize],
+            _ => fill,
+        };
+        let mut shift = |i: isize| {
+            let mut color: ColorRGB = source(self, i - whole);
+            color.blend(source(self, i - whole - 1), fract);
+            self[i as usize] = color;
+        };
+        // Only read pixels that haven't been written to yet.
+        if whole >= 0 {
+            (0..len).rev().for_each(&mut shift);
+        } else {
+            (0..len).for_each(&mut shift);
+        }
+    }
+
+    fn draw_bar(self, start: i32, end: i32, color: ColorRGB, mode: BlendMode) {
+        let (start, end) = (start.min(end), start.max(end));
+        let first: i32 = (start >> 8).max(0);
+        let last: i32 = (end.saturating_add(255) >> 8).min(self.len() as i32);
+        for i in first..last {
+            let p: &mut ColorRGB = &mut self[i as usize];
+            *p = mode.blend(*p, color, coverage_u8(span_coverage(start, end, i)));
+        }
+    }
+}
+
+/// Returns how much of the pixel at `pixel` is covered by the span from `start` to
+/// (exclusive) `end`, where `256` is both a whole pixel and complete coverage.
+///
+/// Positions are fixed point with 8 fractional bits.
+#[inline]
+pub(crate) fn span_coverage(start: i32, end: i32, pixel: i32) -> u32 {
+    let lo: i64 = i64::from(start).max(i64::from(pixel) << 8);
+    let hi: i64 = i64::from(end).min((i64::from(pixel) + 1) << 8);
+    (hi - lo).max(0) as u32
+}
+
+/// Converts a coverage out of `256` to an opacity out of `255`.
+#[inline(always)]
+pub(crate) fn coverage_u8(coverage: u32) -> u8 {
+    coverage.min(255) as u8
+}
+
+/// Blurs a slice (or any line of pixels) one pixel at a time, starting with the already
+/// scaled `carry` from the pixel preceding the slice.
+#[inline]
+pub(crate) fn blur_scalar<'a, I>(pixels: I, keep: u8, seep: u8, carry: ColorRGB)
+where
+    I: IntoIterator<Item = &'a mut ColorRGB>,
+{
+    let mut carry: ColorRGB = carry;
+    let mut iter = pixels.into_iter().peekable();
+    while let Some(cur) = iter.next() {
+        cur.scale(keep);
+        *cur += carry;
+        if let Some(nxt) = iter.peek() {
+            let mut part: ColorRGB = **nxt;
+            part.scale(seep);
+            *cur += part;
+            carry = part;
+        }
+    }
+}
+
+/// Blends each pixel of a slice with `other`, one pixel at a time.
+#[inline]
+pub(crate) fn blend_scalar(pixels: &mut [ColorRGB], other: ColorRGB, amount_of_other: u8) {
+    let p_other: u16 = amount_of_other as u16;
+    let p_this: u16 = (255 - amount_of_other) as u16;
+
+    let partial_r = other.r as u16 * p_other;
+    let partial_g = other.g as u16 * p_other;
+    let partial_b = other.b as u16 * p_other;
+
+    pixels.iter_mut().for_each(|p| {
+        p.r = (((p.r as u16 * p_this) + partial_r) >> 8) as u8;
+        p.g = (((p.g as u16 * p_this) + partial_g) >> 8) as u8;
+        p.b = (((p.b as u16 * p_this) + partial_b) >> 8) as u8;
+    });
 }
 
 /// Same as `scale_u8`, except scale has already had 1 added to it.
 #[inline(always)]
-fn scale_post(i: u8, scale: u16) -> u8 {
+pub(crate) fn scale_post(i: u8, scale: u16) -> u8 {
     (((i as u16) * scale) >> 8) as u8
 }
 
 /// Scales down an entire array by `scale`.
 ///
-/// Rather than scaling each byte individually, scaling is done to two bytes at ones.
+/// The bulk of the array is scaled with SIMD instructions where available (see the `simd`
+/// module). The remaining bytes are scaled two bytes at once rather than individually.
 /// See the method `batch_scale_inner` for how this works, but practically this seems to
 /// be around twice as fast as a iterating with `ColorRGB::scale(u8)`.
 #[inline]
 pub fn batch_scale_bytes(x: &mut [u8], scale: u8) {
+    let done: usize = simd::scale_bytes(x, scale);
     let scalar: u16 = (scale as u16) + 1;
-    let (head, mid, tail) = split_align32(x);
+    let (head, mid, tail) = split_align32(&mut x[done..]);
     head.iter_mut().for_each(|m| *m = scale_post(*m, scalar));
     mid.iter_mut()
         .for_each(|m| *m = batch_scale(*m, scalar as u32));
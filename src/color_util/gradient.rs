@@ -9,7 +9,7 @@ use core::iter::ExactSizeIterator;
 use std::iter::ExactSizeIterator;
 
 use crate::color_util::GradientDirection;
-use crate::math::lerp::ThreePointLerp;
+use crate::math::Lerp;
 use crate::{ColorRGB, HSV};
 
 impl<'a, T, H: 'a> super::GradientFill for T
@@ -105,19 +105,22 @@ pub fn hsv_gradient<'a, C: 'a + From<HSV>, I: IntoIterator<Item = &'a mut C>>(
         start.h = end.h;
     }
 
-    let hue_distance: i16 = dir.into_hue_distance(start.h, end.h);
+    let hue_distance: i32 = dir.into_hue_distance(start.h, end.h);
 
-    let lerp: ThreePointLerp = ThreePointLerp::new()
-        .set_lerp_from_distance(0, start.h, hue_distance)
-        .set_lerp_from_diff(1, start.s, end.s)
-        .set_lerp_from_diff(2, start.v, end.v)
-        .modify_delta(|d| d / (length as i16))
-        .modify_delta(|d| d.wrapping_mul(2));
+    let lerp: Lerp<3> = Lerp::from_distance(
+        [u16::from(start.h), u16::from(start.s), u16::from(start.v)],
+        [
+            hue_distance,
+            i32::from(end.s) - i32::from(start.s),
+            i32::from(end.v) - i32::from(start.v),
+        ],
+        length,
+    );
 
     output
         .into_iter()
         .zip(lerp)
-        .for_each(|(i, hsv)| *i = C::from(HSV::from(hsv)));
+        .for_each(|(i, [h, s, v])| *i = C::from(HSV::new(h as u8, s as u8, v as u8)));
 }
 
 /// Creates a two-color gradient from two RGB values.
@@ -139,17 +142,16 @@ pub fn rgb_gradient<'a, C: 'a + From<ColorRGB>, I: IntoIterator<Item = &'a mut C
         return;
     }
 
-    let lerp: ThreePointLerp = ThreePointLerp::new()
-        .set_lerp_from_diff(0, start.r, end.r)
-        .set_lerp_from_diff(1, start.g, end.g)
-        .set_lerp_from_diff(2, start.b, end.b)
-        .modify_delta(|d| d / (length as i16))
-        .modify_delta(|d| d.wrapping_mul(2));
+    let lerp: Lerp<3> = Lerp::new(
+        [u16::from(start.r), u16::from(start.g), u16::from(start.b)],
+        [u16::from(end.r), u16::from(end.g), u16::from(end.b)],
+        length,
+    );
 
     output
         .into_iter()
         .zip(lerp)
-        .for_each(|(i, rgb)| *i = C::from(ColorRGB::from(rgb)));
+        .for_each(|(i, [r, g, b])| *i = C::from(ColorRGB::new(r as u8, g as u8, b as u8)));
 }
 
 #[cfg(test)]
@@ -185,4 +187,16 @@ mod test {
         out.gradient_fill_to_inclusive(start, end, dir);
         assert_eq!(*out.last().unwrap(), end);
     }
+
+    #[cfg(not(feature = "no-std"))]
+    #[test]
+    fn gradient_rgb_long_test() {
+        let start = ColorRGB::new(255, 0, 10);
+        let end = ColorRGB::new(0, 255, 11);
+        let mut out = vec![ColorRGB::Black; 1021];
+        out.gradient_fill_rgb(start, end);
+        assert_eq!(out[0], start);
+        assert_eq!(out[1020], ColorRGB::new(0, 255, 11));
+        assert_eq!(out[510], ColorRGB::new(128, 127, 10));
+    }
 }
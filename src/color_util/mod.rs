@@ -23,7 +23,7 @@
 //!     - Implemented for all Iterators over `&mut ColorRGB`.
 //! - [`ColorSliceMut`]:
 //!     - Special optimized functions implemented for slices / arrays of [`ColorRGB`]'s.
-//!     - Examples of functions: `blur()`, `fade_to_black()`, `blend()`.
+//!     - Examples of functions: `blur()`, `fade_to_black()`, `blend()`, `blend_with()`.
 //! - [`GradientFill`]:
 //!     - Fills a Gradient from one [`HSV`] to another using Linear Interpolation.
 //!     - Implemented for any iterators implementing `ExactSizeIter`.
@@ -46,6 +46,9 @@
 //!     - Fills an Iterator over `&mut From<HSV>` with a rainbow pattern. This pattern repeats
 //!       forever, starting at a specific hue and taking a user-defined step size for each new
 //!       element.
+//! - [`RainbowFill16`]:
+//!     - The same as `RainbowFill`, but with a 16 bit hue through `HSV16`, for smooth rainbows
+//!       with small step sizes.
 //! - [`RainbowFillSingleCycle`]:
 //!     - Fills an Iterator with a full rainbow cycle.
 //!
@@ -56,17 +59,20 @@
 //! [`GradientFillRGB`]: ./trait.GradientFill.html
 //! [`GradientFillRGBToInclusive`]: ./trait.GradientFillRGBToInclusive.html
 //! [`RainbowFill`]: ./trait.RainbowFill.html
+//! [`RainbowFill16`]: ./trait.RainbowFill16.html
 //! [`RainbowFillSingleCycle`]: ./trait.RainbowFillSingleCycle.html
 //! [`ColorRGB`]: ../struct.ColorRGB.html
 //! [`HSV`]: ../struct.HSV.html
 
 pub mod color_impls;
 pub mod gradient;
+mod simd;
 
 #[doc(hidden)]
 pub mod rainbow;
 
-use crate::{ColorRGB, HSV};
+use crate::math::Q8_8;
+use crate::{BlendMode, ColorRGB, HSV};
 
 /// Useful methods when iterating over `ColorRGB`s.
 ///
@@ -125,6 +131,10 @@ pub trait ColorIterMut: Sized {
 /// using custom SIMD functions. `ColorSliceMut::fade_to_black()` implements this internally,
 /// resulting in dimming that is twice as fast as the above method.
 ///
+/// On `x86` and `x86_64`, these methods use SSE2 or AVX2 instructions, selected at runtime
+/// (or at compile time from the enabled target features under `no-std`). The results are
+/// identical to the non-vectorized implementations.
+///
 /// ```
 /// use cichlid::{prelude::*, ColorRGB};
 /// let mut colors = [ColorRGB::Pink; 50];
@@ -142,6 +152,7 @@ pub trait ColorIterMut: Sized {
 /// let color_slice = &mut colors[0..40];
 /// color_slice.blur(50);
 /// color_slice.blend(ColorRGB::Gold, 120);
+/// color_slice.rotate_by(-3);
 /// ```
 pub trait ColorSliceMut: Sized {
     /// Blurs colors by `blur_amount`.
@@ -161,6 +172,90 @@ pub trait ColorSliceMut: Sized {
 
     /// Applies `ColorRGB::blend()` to the entire slice.
     fn blend(self, other: ColorRGB, amount_of_other: u8);
+
+    /// Sets every color in the slice to `color`.
+    ///
+    /// This is the same as `ColorIterMut::fill()`, but writes multiple colors at once.
+    fn fill_solid(self, color: ColorRGB);
+
+    /// Applies `ColorRGB::hue_rotate()` to the entire slice.
+    fn hue_rotate(self, amount: u8);
+
+    /// Applies `ColorRGB::invert_hue()` to the entire slice.
+    fn invert_hue(self);
+
+    /// Applies `ColorRGB::saturate()` to the entire slice.
+    fn saturate(self, amount: u8);
+
+    /// Applies `ColorRGB::desaturate()` to the entire slice.
+    fn desaturate(self, amount: u8);
+
+    /// Applies `ColorRGB::grayscale()` to the entire slice.
+    fn grayscale(self);
+
+    /// Combines each color of `layer` on top of the matching color in the slice with a
+    /// `BlendMode`, blended in by `opacity`.
+    ///
+    /// If `layer` and the slice differ in length, only the overlapping colors are combined.
+    fn blend_with(self, layer: &[ColorRGB], mode: BlendMode, opacity: u8);
+
+    /// Rotates the colors by `amount` pixels towards the end of the slice, wrapping colors
+    /// past the end back around to the start.
+    ///
+    /// A negative `amount` rotates towards the start of the slice.
+    fn rotate_by(self, amount: isize);
+
+    /// Shifts the colors by `amount` pixels towards the end of the slice, filling the
+    /// vacated pixels with `fill`.
+    ///
+    /// A negative `amount` shifts towards the start of the slice.
+    fn shift_by(self, amount: isize, fill: ColorRGB);
+
+    /// Shifts the colors by a fractional number of pixels towards the end of the slice,
+    /// filling the vacated pixels with `fill`.
+    ///
+    /// Each color is blended with its neighbor by the fractional part of `amount` using
+    /// `ColorRGB::blend()`, so patterns can move smoothly by less than a pixel at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{prelude::*, math::Q8_8, ColorRGB};
+    ///
+    /// let mut colors = [ColorRGB::Black; 4];
+    /// colors[1] = ColorRGB::White;
+    ///
+    /// // Move a quarter of a pixel towards the end.
+    /// colors.shift_fract(Q8_8::new(0, 64), ColorRGB::Black);
+    /// assert_eq!(colors[1], ColorRGB::new(191, 191, 191));
+    /// assert_eq!(colors[2], ColorRGB::new(64, 64, 64));
+    /// ```
+    fn shift_fract(self, amount: Q8_8, fill: ColorRGB);
+
+    /// Draws a bar of `color` from `start` to (exclusive) `end`, combined on top of the slice
+    /// with a `BlendMode`.
+    ///
+    /// Positions are fixed point with 8 fractional bits, so `256` is the start of the second
+    /// pixel. Pixels only partially covered by the bar are blended in by how much of them is
+    /// covered, and any part of the bar outside of the slice is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{prelude::*, BlendMode, ColorRGB};
+    ///
+    /// let mut colors = [ColorRGB::Black; 6];
+    ///
+    /// // From 1.5 to 4.25 pixels.
+    /// colors.draw_bar(384, 1088, ColorRGB::White, BlendMode::Normal);
+    /// assert_eq!(colors[0], ColorRGB::Black);
+    /// assert_eq!(colors[1], ColorRGB::new(128, 128, 128));
+    /// assert_eq!(colors[2], ColorRGB::White);
+    /// assert_eq!(colors[3], ColorRGB::White);
+    /// assert_eq!(colors[4], ColorRGB::new(64, 64, 64));
+    /// assert_eq!(colors[5], ColorRGB::Black);
+    /// ```
+    fn draw_bar(self, start: i32, end: i32, color: ColorRGB, mode: BlendMode);
 }
 
 /// Fills an iterable object with a gradient from the `HSV` values `start` to `finish`, exclusive of the
@@ -250,6 +345,40 @@ pub trait RainbowFill: Sized {
     fn rainbow_fill_with_sat_val(self, start_hue: u8, hue_delta: u16, sat: u8, val: u8);
 }
 
+/// Fills an iterable object with a rainbow hue of a desired step size, using a 16 bit hue.
+///
+/// Unlike `RainbowFill`, which drops the fractional byte of each hue, every element is set
+/// from a `HSV16` with the full accumulated hue. Small step sizes then change smoothly from
+/// element to element, rather than repeating each hue before jumping to the next.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::{prelude::*, ColorRGB};
+///
+/// // Steps of half a hue repeat each hue with `RainbowFill`.
+/// let mut coarse = [ColorRGB::Black; 4];
+/// coarse.rainbow_fill(0, 0x0080);
+/// assert_eq!(coarse[0], coarse[1]);
+///
+/// let mut smooth = [ColorRGB::Black; 4];
+/// smooth.rainbow_fill16(0, 0x0080);
+/// assert_eq!(smooth[0], ColorRGB::Red);
+/// assert_ne!(smooth[0], smooth[1]);
+/// ```
+pub trait RainbowFill16: Sized {
+    /// Fills an object with a rainbow gradient hue of a desired step size and from a desired
+    /// 16 bit starting hue.
+    #[inline(always)]
+    fn rainbow_fill16(self, start_hue: u16, hue_delta: u16) {
+        self.rainbow_fill16_with_sat_val(start_hue, hue_delta, 255, 255);
+    }
+
+    /// Fills an object with a rainbow gradient hue of a desired step size and from a desired
+    /// 16 bit starting hue and constant additional saturation and value.
+    fn rainbow_fill16_with_sat_val(self, start_hue: u16, hue_delta: u16, sat: u8, val: u8);
+}
+
 /// Fills an iterable object with a single complete rainbow.
 ///
 /// If the the rainbow is needed backwards, try calling `iter.rev()` before calling this
@@ -272,6 +401,7 @@ pub enum HueDirection {
 
 /// Possible Directions around the color wheel a gradient can go.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum GradientDirection {
     /// Goes around the color wheel clockwise. ala, Hue increases as the gradient progresses,
@@ -314,17 +444,13 @@ impl GradientDirection {
         }
     }
 
-    /// Returns the difference between hues.
+    /// Returns the signed difference between hues.
     #[inline(always)]
-    fn into_hue_distance(self, start_hue: u8, end_hue: u8) -> i16 {
+    pub(crate) fn into_hue_distance(self, start_hue: u8, end_hue: u8) -> i32 {
         let hue_diff: u8 = end_hue.wrapping_sub(start_hue);
         match self.into_hue_direction(hue_diff) {
-            HueDirection::Forward => i16::from(hue_diff) << 7,
-            HueDirection::Backwards => {
-                let hue_diff: u8 = (256u16).wrapping_sub(u16::from(hue_diff)) as u8;
-                let hue_diff: i16 = i16::from(hue_diff) << 7;
-                -hue_diff
-            }
+            HueDirection::Forward => i32::from(hue_diff),
+            HueDirection::Backwards => -i32::from(hue_diff.wrapping_neg()),
         }
     }
 }
@@ -375,6 +501,70 @@ mod test {
         }
     }
 
+    #[test]
+    fn rotate_shift_test() {
+        let mut arr = [ColorRGB::Red, ColorRGB::Green, ColorRGB::Blue, ColorRGB::White];
+        arr.rotate_by(5);
+        assert_eq!(arr[1], ColorRGB::Red);
+        arr.rotate_by(-9);
+        assert_eq!(arr[0], ColorRGB::Red);
+
+        arr.shift_by(2, ColorRGB::Black);
+        assert_eq!(
+            arr,
+            [ColorRGB::Black, ColorRGB::Black, ColorRGB::Red, ColorRGB::Green]
+        );
+        arr.shift_by(-3, ColorRGB::Gold);
+        assert_eq!(arr, [ColorRGB::Green, ColorRGB::Gold, ColorRGB::Gold, ColorRGB::Gold]);
+        arr.shift_by(isize::MIN, ColorRGB::Black);
+        assert_eq!(arr, [ColorRGB::Black; 4]);
+    }
+
+    #[test]
+    fn shift_fract_test() {
+        let mut arr = [ColorRGB::Black; 8];
+        arr[3] = ColorRGB::White;
+        let mut whole = arr;
+        arr.shift_fract(Q8_8::from_int(2), ColorRGB::Black);
+        whole.shift_by(2, ColorRGB::Black);
+        assert_eq!(arr, whole);
+
+        // Two and a half pixels back.
+        arr.shift_fract(Q8_8::new(-3, 128), ColorRGB::Black);
+        assert_eq!(arr[2], ColorRGB::new(127, 127, 127));
+        assert_eq!(arr[3], ColorRGB::new(128, 128, 128));
+        assert_eq!(arr[7], ColorRGB::Black);
+
+        // The color is split between the two pixels it lands between.
+        let mut arr = [ColorRGB::Black; 8];
+        arr[0] = ColorRGB::White;
+        arr.shift_fract(Q8_8::ZERO, ColorRGB::Black);
+        assert_eq!(arr[0], ColorRGB::White);
+        let mut total: u32 = 0;
+        arr.shift_fract(Q8_8::from_bits(300), ColorRGB::Black);
+        arr.iter().for_each(|c| total += u32::from(c.b));
+        assert_eq!(arr[0], ColorRGB::Black);
+        assert_eq!(arr[1], ColorRGB::new(211, 211, 211));
+        assert_eq!(total, 255);
+    }
+
+    #[test]
+    fn draw_bar_test() {
+        let mut arr = [ColorRGB::Black; 8];
+        arr.draw_bar(-1000, 512, ColorRGB::Red, BlendMode::Normal);
+        arr.draw_bar(2048, 1792, ColorRGB::Blue, BlendMode::Normal);
+        arr.draw_bar(1800, 1800, ColorRGB::Green, BlendMode::Normal);
+        arr.draw_bar(1536, i32::MAX, ColorRGB::Blue, BlendMode::Add);
+        assert_eq!(&arr[..3], &[ColorRGB::Red, ColorRGB::Red, ColorRGB::Black]);
+        assert_eq!(arr[6], ColorRGB::Blue);
+        assert_eq!(arr[7], ColorRGB::Blue);
+
+        // Partial coverage of a single pixel.
+        let mut arr = [ColorRGB::Black; 2];
+        arr.draw_bar(64, 192, ColorRGB::White, BlendMode::Normal);
+        assert_eq!(arr, [ColorRGB::new(128, 128, 128), ColorRGB::Black]);
+    }
+
     #[test]
     fn blur_test_long() {
         let mut arr = [ColorRGB::BlueViolet; 256];
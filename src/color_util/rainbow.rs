@@ -1,6 +1,6 @@
 //! Rainbow gradient impls.
 
-use crate::HSV;
+use crate::{HSV, HSV16};
 
 impl<'a, T, H: 'a> super::RainbowFill for T
 where
@@ -20,6 +20,20 @@ where
     }
 }
 
+impl<'a, T, H: 'a> super::RainbowFill16 for T
+where
+    T: IntoIterator<Item = &'a mut H>,
+    H: From<HSV16>,
+{
+    fn rainbow_fill16_with_sat_val(self, start_hue: u16, hue_delta: u16, sat: u8, val: u8) {
+        let mut hue: u16 = start_hue;
+        self.into_iter().for_each(|i| {
+            *i = H::from(HSV16::new(hue, sat, val));
+            hue = hue.wrapping_add(hue_delta);
+        });
+    }
+}
+
 impl<'a, T, H: 'a> super::RainbowFillSingleCycle for T
 where
     T: IntoIterator<Item = &'a mut H>,
@@ -0,0 +1,104 @@
+//! Vectorized fast paths for `ColorSliceMut`.
+//!
+//! Each function here processes as much of the front of a slice as it can with the widest
+//! instruction set available, and returns the number of elements it handled. Whatever remains
+//! is left to the scalar implementations in `color_impls`, which these are bit-exact with.
+//!
+//! On `x86` and `x86_64`, SSE2 or AVX2 intrinsics are used. With the `nightly` feature, other
+//! targets with 128 bit vector registers (NEON, WebAssembly SIMD, and AltiVec) use the portable
+//! `core::simd` implementations instead. On every other target, including microcontrollers
+//! without a vector unit where `core::simd` would be lowered back to scalar code, every
+//! function handles zero elements.
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+#[cfg(all(
+    feature = "nightly",
+    any(
+        test,
+        target_feature = "neon",
+        target_feature = "simd128",
+        target_feature = "altivec"
+    )
+))]
+mod portable;
+
+#[cfg(feature = "no-std")]
+use core::slice;
+#[cfg(not(feature = "no-std"))]
+use std::slice;
+
+use crate::ColorRGB;
+
+// Calls the implementation for the current backend, or returns zero if there is none.
+macro_rules! simd_dispatch {
+    ($fname:ident($($arg:ident),*)) => {
+        match () {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            () => x86::$fname($($arg),*),
+
+            #[cfg(all(
+                feature = "nightly",
+                any(
+                    target_feature = "neon",
+                    target_feature = "simd128",
+                    target_feature = "altivec"
+                )
+            ))]
+            () => portable::$fname::<{ portable::LANES }>($($arg),*),
+
+            #[cfg(not(any(
+                target_arch = "x86",
+                target_arch = "x86_64",
+                all(
+                    feature = "nightly",
+                    any(
+                        target_feature = "neon",
+                        target_feature = "simd128",
+                        target_feature = "altivec"
+                    )
+                )
+            )))]
+            () => {
+                let _ = ($($arg),*);
+                0
+            }
+        }
+    };
+}
+
+/// Scales the front of `bytes` by `scale`, returning the number of bytes scaled.
+#[inline]
+pub fn scale_bytes(bytes: &mut [u8], scale: u8) -> usize {
+    simd_dispatch!(scale_bytes(bytes, scale))
+}
+
+/// Blends the front of `pixels` with `other`, returning the number of pixels blended.
+#[inline]
+pub fn blend(pixels: &mut [ColorRGB], other: ColorRGB, amount_of_other: u8) -> usize {
+    simd_dispatch!(blend(pixels, other, amount_of_other))
+}
+
+/// Fills the front of `pixels` with `color`, returning the number of pixels filled.
+#[inline]
+pub fn fill(pixels: &mut [ColorRGB], color: ColorRGB) -> usize {
+    simd_dispatch!(fill(pixels, color))
+}
+
+/// Blurs the front of `pixels`, returning the number of pixels blurred.
+///
+/// The pixel directly after the blurred section is left untouched, as the scalar blur
+/// needs its original value to continue.
+#[inline]
+pub fn blur(pixels: &mut [ColorRGB], keep: u8, seep: u8) -> usize {
+    simd_dispatch!(blur(pixels, keep, seep))
+}
+
+/// Reinterprets a slice of `ColorRGB`s as its raw bytes.
+#[allow(dead_code)]
+#[inline(always)]
+fn as_bytes_mut(pixels: &mut [ColorRGB]) -> &mut [u8] {
+    let len: usize = pixels.len() * 3;
+    unsafe { slice::from_raw_parts_mut(pixels.as_mut_ptr() as *mut u8, len) }
+}
@@ -0,0 +1,228 @@
+//! Portable SIMD implementations using `core::simd`, enabled by the `nightly` feature.
+//!
+//! These are compiled for whichever vector instructions the target supports. Each function
+//! takes the number of bytes per vector as `LANES`, and pixel operations work over blocks of
+//! `LANES` pixels (three vectors), keeping the repeating three byte pattern of a `ColorRGB`
+//! lined up with each block.
+
+#[cfg(feature = "no-std")]
+use core::array;
+#[cfg(not(feature = "no-std"))]
+use std::array;
+
+#[cfg(feature = "no-std")]
+use core::simd::{num::SimdUint, Simd, SimdElement};
+#[cfg(not(feature = "no-std"))]
+use std::simd::{num::SimdUint, Simd, SimdElement};
+
+use super::as_bytes_mut;
+use crate::ColorRGB;
+
+/// Number of bytes per vector, filling the 128 bit registers of the targets this is used on.
+///
+/// Wider registers are only found on `x86`, which has its own implementation. `32` lanes is
+/// supported as well, but on these targets is split into pairs of registers.
+pub const LANES: usize = 16;
+
+type Bytes<const LANES: usize> = Simd<u8, LANES>;
+type Wide<const LANES: usize> = Simd<u16, LANES>;
+
+/// Computes `(byte * scalar) >> 8` for each byte.
+#[inline(always)]
+fn scale<const LANES: usize>(v: Bytes<LANES>, scalar: Wide<LANES>) -> Bytes<LANES> {
+    ((v.cast::<u16>() * scalar) >> Wide::splat(8)).cast::<u8>()
+}
+
+/// Each of the three vectors making up a block of `LANES` pixels, with channels mapped by `f`.
+#[inline(always)]
+fn block_pattern<T, F, const LANES: usize>(f: F) -> [Simd<T, LANES>; 3]
+where
+    T: SimdElement,
+    F: Fn(usize) -> T,
+{
+    array::from_fn(|k| Simd::from_array(array::from_fn(|i| f((k * LANES + i) % 3))))
+}
+
+pub fn scale_bytes<const LANES: usize>(bytes: &mut [u8], scale_by: u8) -> usize {
+    let done = bytes.len() - bytes.len() % LANES;
+    let scalar = Wide::<LANES>::splat(u16::from(scale_by) + 1);
+    bytes
+        .chunks_exact_mut(LANES)
+        .for_each(|chunk| scale(Bytes::from_slice(chunk), scalar).copy_to_slice(chunk));
+    done
+}
+
+pub fn blend<const LANES: usize>(
+    pixels: &mut [ColorRGB],
+    other: ColorRGB,
+    amount_of_other: u8,
+) -> usize {
+    let done = pixels.len() - pixels.len() % LANES;
+    let partials: [Wide<LANES>; 3] =
+        block_pattern(|c| u16::from(other[c]) * u16::from(amount_of_other));
+    let p_this = Wide::<LANES>::splat(255 - u16::from(amount_of_other));
+
+    as_bytes_mut(pixels)
+        .chunks_exact_mut(LANES * 3)
+        .for_each(|block| {
+            block
+                .chunks_exact_mut(LANES)
+                .zip(partials.iter())
+                .for_each(|(chunk, partial)| {
+                    let v = Bytes::<LANES>::from_slice(chunk).cast::<u16>();
+                    let out = ((v * p_this + *partial) >> Wide::splat(8)).cast::<u8>();
+                    out.copy_to_slice(chunk);
+                })
+        });
+    done
+}
+
+pub fn fill<const LANES: usize>(pixels: &mut [ColorRGB], color: ColorRGB) -> usize {
+    let done = pixels.len() - pixels.len() % LANES;
+    let pattern: [Bytes<LANES>; 3] = block_pattern(|c| color[c]);
+
+    as_bytes_mut(pixels)
+        .chunks_exact_mut(LANES * 3)
+        .for_each(|block| {
+            block
+                .chunks_exact_mut(LANES)
+                .zip(pattern.iter())
+                .for_each(|(chunk, p)| p.copy_to_slice(chunk))
+        });
+    done
+}
+
+pub fn blur<const LANES: usize>(pixels: &mut [ColorRGB], keep: u8, seep: u8) -> usize {
+    // The pixel after the last block is read, so it must exist.
+    let len = pixels.len().saturating_sub(1);
+    let done = len - len % LANES;
+    let keep = Wide::<LANES>::splat(u16::from(keep) + 1);
+    let seep = Wide::<LANES>::splat(u16::from(seep) + 1);
+    // The first pixel has nothing carried over into it.
+    let first_carry = Bytes::<LANES>::from_array(array::from_fn(|i| if i < 3 { 0 } else { 255 }));
+
+    let bytes = as_bytes_mut(pixels);
+    for j in (0..done * 3).step_by(LANES) {
+        let v = Bytes::<LANES>::from_slice(&bytes[j..j + LANES]);
+        let next = Bytes::<LANES>::from_slice(&bytes[j + 3..j + 3 + LANES]);
+        let mut carry = scale(v, seep);
+        if j == 0 {
+            carry &= first_carry;
+        }
+        let out = scale(v, keep).saturating_add(carry);
+        let out = out.saturating_add(scale(next, seep));
+        out.copy_to_slice(&mut bytes[j..j + LANES]);
+    }
+    done
+}
+
+#[cfg(all(test, not(feature = "no-std")))]
+mod test {
+    use super::*;
+    use crate::color_util::color_impls::{blend_scalar, blur_scalar, scale_post};
+
+    fn rand_change(seed: &mut u64) -> u64 {
+        *seed ^= *seed >> 12;
+        *seed ^= *seed << 25;
+        *seed ^= *seed >> 27;
+        seed.wrapping_mul(2_685_821_657_736_338_717)
+    }
+
+    fn rand_pixels(seed: &mut u64, len: usize) -> Vec<ColorRGB> {
+        (0..len)
+            .map(|_| ColorRGB::from_color_code(rand_change(seed) as u32))
+            .collect()
+    }
+
+    fn scale_bytes_matches_scalar<const LANES: usize>() {
+        let mut seed: u64 = 4_410_293_847;
+        for len in 0..300 {
+            let buffer: Vec<u8> = (0..len).map(|_| rand_change(&mut seed) as u8).collect();
+            for scale_by in (0..=255).step_by(7) {
+                let mut expected = buffer.clone();
+                expected
+                    .iter_mut()
+                    .for_each(|v| *v = scale_post(*v, u16::from(scale_by) + 1));
+
+                let mut actual = buffer.clone();
+                let done = scale_bytes::<LANES>(&mut actual, scale_by);
+                actual[done..]
+                    .iter_mut()
+                    .for_each(|v| *v = scale_post(*v, u16::from(scale_by) + 1));
+                assert_eq!(expected, actual, "len: {}, scale: {}", len, scale_by);
+            }
+        }
+    }
+
+    fn blend_matches_scalar<const LANES: usize>() {
+        let mut seed: u64 = 81_003_499_120;
+        for len in 0..150 {
+            let buffer = rand_pixels(&mut seed, len);
+            let other = ColorRGB::from_color_code(rand_change(&mut seed) as u32);
+            for amount in (0..=255).step_by(5) {
+                let mut expected = buffer.clone();
+                blend_scalar(&mut expected, other, amount);
+
+                let mut actual = buffer.clone();
+                let done = blend::<LANES>(&mut actual, other, amount);
+                blend_scalar(&mut actual[done..], other, amount);
+                assert_eq!(expected, actual, "len: {}, amount: {}", len, amount);
+            }
+        }
+    }
+
+    fn fill_matches_scalar<const LANES: usize>() {
+        let mut seed: u64 = 12_300_984_551;
+        for len in 0..150 {
+            let buffer = rand_pixels(&mut seed, len);
+            let color = ColorRGB::from_color_code(rand_change(&mut seed) as u32);
+
+            let mut actual = buffer.clone();
+            let done = fill::<LANES>(&mut actual, color);
+            assert_eq!(&actual[done..], &buffer[done..]);
+            assert!(actual[..done].iter().all(|p| *p == color));
+        }
+    }
+
+    fn blur_matches_scalar<const LANES: usize>() {
+        let mut seed: u64 = 559_201_003_817;
+        for len in 0..150 {
+            let buffer = rand_pixels(&mut seed, len);
+            for blur_amount in (0..=255).step_by(3) {
+                let keep: u8 = 255 - blur_amount;
+                let seep: u8 = blur_amount >> 1;
+                let mut expected = buffer.clone();
+                blur_scalar(&mut expected, keep, seep, ColorRGB::Black);
+
+                let mut actual = buffer.clone();
+                let done = blur::<LANES>(&mut actual, keep, seep);
+                let carry = match done {
+                    0 => ColorRGB::Black,
+                    _ => {
+                        let mut part = actual[done];
+                        part.scale(seep);
+                        part
+                    }
+                };
+                blur_scalar(&mut actual[done..], keep, seep, carry);
+                assert_eq!(expected, actual, "len: {}, blur: {}", len, blur_amount);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_scalar() {
+        scale_bytes_matches_scalar::<LANES>();
+        blend_matches_scalar::<LANES>();
+        fill_matches_scalar::<LANES>();
+        blur_matches_scalar::<LANES>();
+    }
+
+    #[test]
+    fn matches_scalar_32_lanes() {
+        scale_bytes_matches_scalar::<32>();
+        blend_matches_scalar::<32>();
+        fill_matches_scalar::<32>();
+        blur_matches_scalar::<32>();
+    }
+}
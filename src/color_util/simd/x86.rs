@@ -0,0 +1,474 @@
+//! SSE2 and AVX2 implementations for `x86` and `x86_64`.
+//!
+//! When `std` is available, the widest supported instruction set is detected at runtime.
+//! Under `no-std`, the instruction set is instead chosen from the target features enabled at
+//! compile time (for example, with `RUSTFLAGS=-Ctarget-cpu=native`).
+//!
+//! SSE2 functions operate over blocks of 16 pixels (three 16 byte registers), while AVX2
+//! functions use blocks of 32 pixels (three 32 byte registers). This keeps the repeating
+//! three byte pattern of a `ColorRGB` lined up with the start of each block.
+
+#[cfg(all(target_arch = "x86", feature = "no-std"))]
+use core::arch::x86::*;
+#[cfg(all(target_arch = "x86", not(feature = "no-std")))]
+use std::arch::x86::*;
+
+#[cfg(all(target_arch = "x86_64", feature = "no-std"))]
+use core::arch::x86_64::*;
+#[cfg(all(target_arch = "x86_64", not(feature = "no-std")))]
+use std::arch::x86_64::*;
+
+use super::as_bytes_mut;
+use crate::ColorRGB;
+
+/// Instruction sets with an implementation, from widest to narrowest.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Level {
+    Avx2,
+    Sse2,
+    Scalar,
+}
+
+#[inline]
+fn level() -> Level {
+    if has_avx2() {
+        Level::Avx2
+    } else if has_sse2() {
+        Level::Sse2
+    } else {
+        Level::Scalar
+    }
+}
+
+#[inline(always)]
+fn has_avx2() -> bool {
+    match () {
+        #[cfg(not(feature = "no-std"))]
+        () => is_x86_feature_detected!("avx2"),
+
+        #[cfg(feature = "no-std")]
+        () => cfg!(target_feature = "avx2"),
+    }
+}
+
+#[inline(always)]
+fn has_sse2() -> bool {
+    match () {
+        #[cfg(not(feature = "no-std"))]
+        () => is_x86_feature_detected!("sse2"),
+
+        #[cfg(feature = "no-std")]
+        () => cfg!(target_feature = "sse2"),
+    }
+}
+
+pub fn scale_bytes(bytes: &mut [u8], scale: u8) -> usize {
+    match level() {
+        Level::Avx2 => unsafe { scale_bytes_avx2(bytes, scale) },
+        Level::Sse2 => unsafe { scale_bytes_sse2(bytes, scale) },
+        Level::Scalar => 0,
+    }
+}
+
+pub fn blend(pixels: &mut [ColorRGB], other: ColorRGB, amount_of_other: u8) -> usize {
+    match level() {
+        Level::Avx2 => unsafe { blend_avx2(pixels, other, amount_of_other) },
+        Level::Sse2 => unsafe { blend_sse2(pixels, other, amount_of_other) },
+        Level::Scalar => 0,
+    }
+}
+
+pub fn fill(pixels: &mut [ColorRGB], color: ColorRGB) -> usize {
+    match level() {
+        Level::Avx2 => unsafe { fill_avx2(pixels, color) },
+        Level::Sse2 => unsafe { fill_sse2(pixels, color) },
+        Level::Scalar => 0,
+    }
+}
+
+pub fn blur(pixels: &mut [ColorRGB], keep: u8, seep: u8) -> usize {
+    match level() {
+        Level::Avx2 => unsafe { blur_avx2(pixels, keep, seep) },
+        Level::Sse2 => unsafe { blur_sse2(pixels, keep, seep) },
+        Level::Scalar => 0,
+    }
+}
+
+/// `other * amount_of_other` for each byte of 32 consecutive pixels.
+#[inline(always)]
+fn blend_partials(other: ColorRGB, amount_of_other: u8) -> [u16; 96] {
+    let mut partials = [0u16; 96];
+    partials
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, p)| *p = u16::from(other[i % 3]) * u16::from(amount_of_other));
+    partials
+}
+
+/// Each byte of 32 consecutive pixels.
+#[inline(always)]
+fn fill_pattern(color: ColorRGB) -> [u8; 96] {
+    let mut pattern = [0u8; 96];
+    pattern
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, p)| *p = color[i % 3]);
+    pattern
+}
+
+// SSE2
+
+/// Computes `(byte * scalar) >> 8` for each byte, where `scalar` holds 16 bit lanes.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn scale_sse2(v: __m128i, scalar: __m128i) -> __m128i {
+    let zero = _mm_setzero_si128();
+    let lo = _mm_srli_epi16(_mm_mullo_epi16(_mm_unpacklo_epi8(v, zero), scalar), 8);
+    let hi = _mm_srli_epi16(_mm_mullo_epi16(_mm_unpackhi_epi8(v, zero), scalar), 8);
+    _mm_packus_epi16(lo, hi)
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn scale_bytes_sse2(bytes: &mut [u8], scale: u8) -> usize {
+    let scalar = _mm_set1_epi16(i16::from(scale) + 1);
+    let done = bytes.len() & !15;
+    bytes.chunks_exact_mut(16).for_each(|chunk| {
+        let ptr = chunk.as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(ptr, scale_sse2(_mm_loadu_si128(ptr), scalar));
+    });
+    done
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn blend_sse2(pixels: &mut [ColorRGB], other: ColorRGB, amount_of_other: u8) -> usize {
+    let done = pixels.len() & !15;
+    let partials = blend_partials(other, amount_of_other);
+    let partials: [(__m128i, __m128i); 3] = [
+        load_unpacked_sse2(&partials[0..16]),
+        load_unpacked_sse2(&partials[16..32]),
+        load_unpacked_sse2(&partials[32..48]),
+    ];
+    let p_this = _mm_set1_epi16(255 - i16::from(amount_of_other));
+    let zero = _mm_setzero_si128();
+
+    as_bytes_mut(pixels).chunks_exact_mut(48).for_each(|block| {
+        for (k, (partial_lo, partial_hi)) in partials.iter().enumerate() {
+            let ptr = block.as_mut_ptr().add(k * 16) as *mut __m128i;
+            let v = _mm_loadu_si128(ptr);
+            let lo = _mm_mullo_epi16(_mm_unpacklo_epi8(v, zero), p_this);
+            let hi = _mm_mullo_epi16(_mm_unpackhi_epi8(v, zero), p_this);
+            let lo = _mm_srli_epi16(_mm_add_epi16(lo, *partial_lo), 8);
+            let hi = _mm_srli_epi16(_mm_add_epi16(hi, *partial_hi), 8);
+            _mm_storeu_si128(ptr, _mm_packus_epi16(lo, hi));
+        }
+    });
+    done
+}
+
+/// Loads the 16 bit lanes matching `_mm_unpacklo_epi8` and `_mm_unpackhi_epi8` of 16 bytes.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn load_unpacked_sse2(lanes: &[u16]) -> (__m128i, __m128i) {
+    debug_assert_eq!(lanes.len(), 16);
+    let ptr = lanes.as_ptr() as *const __m128i;
+    (_mm_loadu_si128(ptr), _mm_loadu_si128(ptr.add(1)))
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn fill_sse2(pixels: &mut [ColorRGB], color: ColorRGB) -> usize {
+    let done = pixels.len() & !15;
+    let pattern = fill_pattern(color);
+    let ptr = pattern.as_ptr() as *const __m128i;
+    let pattern = [
+        _mm_loadu_si128(ptr),
+        _mm_loadu_si128(ptr.add(1)),
+        _mm_loadu_si128(ptr.add(2)),
+    ];
+
+    as_bytes_mut(pixels).chunks_exact_mut(48).for_each(|block| {
+        let ptr = block.as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(ptr, pattern[0]);
+        _mm_storeu_si128(ptr.add(1), pattern[1]);
+        _mm_storeu_si128(ptr.add(2), pattern[2]);
+    });
+    done
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn blur_sse2(pixels: &mut [ColorRGB], keep: u8, seep: u8) -> usize {
+    // The pixel after the last block is read, so it must exist.
+    let done = pixels.len().saturating_sub(1) & !15;
+    let keep = _mm_set1_epi16(i16::from(keep) + 1);
+    let seep = _mm_set1_epi16(i16::from(seep) + 1);
+    // The first pixel has nothing carried over into it.
+    let first_carry = _mm_setr_epi8(0, 0, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1);
+
+    let ptr = pixels.as_mut_ptr() as *mut u8;
+    for i in 0..(done * 3) / 16 {
+        let cur = ptr.add(i * 16);
+        let v = _mm_loadu_si128(cur as *const __m128i);
+        let next = _mm_loadu_si128(cur.add(3) as *const __m128i);
+        let mut carry = scale_sse2(v, seep);
+        if i == 0 {
+            carry = _mm_and_si128(carry, first_carry);
+        }
+        let out = _mm_adds_epu8(scale_sse2(v, keep), carry);
+        let out = _mm_adds_epu8(out, scale_sse2(next, seep));
+        _mm_storeu_si128(cur as *mut __m128i, out);
+    }
+    done
+}
+
+// AVX2
+
+/// Computes `(byte * scalar) >> 8` for each byte, where `scalar` holds 16 bit lanes.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn scale_avx2(v: __m256i, scalar: __m256i) -> __m256i {
+    let zero = _mm256_setzero_si256();
+    let lo = _mm256_srli_epi16(_mm256_mullo_epi16(_mm256_unpacklo_epi8(v, zero), scalar), 8);
+    let hi = _mm256_srli_epi16(_mm256_mullo_epi16(_mm256_unpackhi_epi8(v, zero), scalar), 8);
+    _mm256_packus_epi16(lo, hi)
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn scale_bytes_avx2(bytes: &mut [u8], scale: u8) -> usize {
+    let scalar = _mm256_set1_epi16(i16::from(scale) + 1);
+    let done = bytes.len() & !31;
+    bytes.chunks_exact_mut(32).for_each(|chunk| {
+        let ptr = chunk.as_mut_ptr() as *mut __m256i;
+        _mm256_storeu_si256(ptr, scale_avx2(_mm256_loadu_si256(ptr), scalar));
+    });
+    done
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn blend_avx2(pixels: &mut [ColorRGB], other: ColorRGB, amount_of_other: u8) -> usize {
+    let done = pixels.len() & !31;
+    let partials = blend_partials(other, amount_of_other);
+    let partials: [(__m256i, __m256i); 3] = [
+        load_unpacked_avx2(&partials[0..32]),
+        load_unpacked_avx2(&partials[32..64]),
+        load_unpacked_avx2(&partials[64..96]),
+    ];
+    let p_this = _mm256_set1_epi16(255 - i16::from(amount_of_other));
+    let zero = _mm256_setzero_si256();
+
+    as_bytes_mut(pixels).chunks_exact_mut(96).for_each(|block| {
+        for (k, (partial_lo, partial_hi)) in partials.iter().enumerate() {
+            let ptr = block.as_mut_ptr().add(k * 32) as *mut __m256i;
+            let v = _mm256_loadu_si256(ptr);
+            let lo = _mm256_mullo_epi16(_mm256_unpacklo_epi8(v, zero), p_this);
+            let hi = _mm256_mullo_epi16(_mm256_unpackhi_epi8(v, zero), p_this);
+            let lo = _mm256_srli_epi16(_mm256_add_epi16(lo, *partial_lo), 8);
+            let hi = _mm256_srli_epi16(_mm256_add_epi16(hi, *partial_hi), 8);
+            _mm256_storeu_si256(ptr, _mm256_packus_epi16(lo, hi));
+        }
+    });
+    done
+}
+
+/// Loads the 16 bit lanes matching `_mm256_unpacklo_epi8` and `_mm256_unpackhi_epi8` of 32
+/// bytes.
+///
+/// AVX2 unpacks within each 128 bit half, so the low lanes are bytes `0..8` and `16..24`,
+/// while the high lanes are bytes `8..16` and `24..32`.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn load_unpacked_avx2(lanes: &[u16]) -> (__m256i, __m256i) {
+    debug_assert_eq!(lanes.len(), 32);
+    let ptr = lanes.as_ptr() as *const __m128i;
+    let lo = _mm256_setr_m128i(_mm_loadu_si128(ptr), _mm_loadu_si128(ptr.add(2)));
+    let hi = _mm256_setr_m128i(_mm_loadu_si128(ptr.add(1)), _mm_loadu_si128(ptr.add(3)));
+    (lo, hi)
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn fill_avx2(pixels: &mut [ColorRGB], color: ColorRGB) -> usize {
+    let done = pixels.len() & !31;
+    let pattern = fill_pattern(color);
+    let ptr = pattern.as_ptr() as *const __m256i;
+    let pattern = [
+        _mm256_loadu_si256(ptr),
+        _mm256_loadu_si256(ptr.add(1)),
+        _mm256_loadu_si256(ptr.add(2)),
+    ];
+
+    as_bytes_mut(pixels).chunks_exact_mut(96).for_each(|block| {
+        let ptr = block.as_mut_ptr() as *mut __m256i;
+        _mm256_storeu_si256(ptr, pattern[0]);
+        _mm256_storeu_si256(ptr.add(1), pattern[1]);
+        _mm256_storeu_si256(ptr.add(2), pattern[2]);
+    });
+    done
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn blur_avx2(pixels: &mut [ColorRGB], keep: u8, seep: u8) -> usize {
+    // The pixel after the last block is read, so it must exist.
+    let done = pixels.len().saturating_sub(1) & !31;
+    let keep = _mm256_set1_epi16(i16::from(keep) + 1);
+    let seep = _mm256_set1_epi16(i16::from(seep) + 1);
+    // The first pixel has nothing carried over into it.
+    let first_carry = _mm256_setr_epi8(
+        0, 0, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1, -1, -1, -1, -1,
+    );
+
+    let ptr = pixels.as_mut_ptr() as *mut u8;
+    for i in 0..(done * 3) / 32 {
+        let cur = ptr.add(i * 32);
+        let v = _mm256_loadu_si256(cur as *const __m256i);
+        let next = _mm256_loadu_si256(cur.add(3) as *const __m256i);
+        let mut carry = scale_avx2(v, seep);
+        if i == 0 {
+            carry = _mm256_and_si256(carry, first_carry);
+        }
+        let out = _mm256_adds_epu8(scale_avx2(v, keep), carry);
+        let out = _mm256_adds_epu8(out, scale_avx2(next, seep));
+        _mm256_storeu_si256(cur as *mut __m256i, out);
+    }
+    done
+}
+
+#[cfg(all(test, not(feature = "no-std")))]
+mod test {
+    use super::*;
+    use crate::color_util::color_impls::{blend_scalar, blur_scalar, scale_post};
+
+    fn rand_change(seed: &mut u64) -> u64 {
+        *seed ^= *seed >> 12;
+        *seed ^= *seed << 25;
+        *seed ^= *seed >> 27;
+        seed.wrapping_mul(2_685_821_657_736_338_717)
+    }
+
+    fn rand_pixels(seed: &mut u64, len: usize) -> Vec<ColorRGB> {
+        (0..len)
+            .map(|_| ColorRGB::from_color_code(rand_change(seed) as u32))
+            .collect()
+    }
+
+    fn levels() -> Vec<Level> {
+        let mut levels = Vec::new();
+        if has_sse2() {
+            levels.push(Level::Sse2);
+        }
+        if has_avx2() {
+            levels.push(Level::Avx2);
+        }
+        levels
+    }
+
+    #[test]
+    fn scale_bytes_matches_scalar() {
+        let mut seed: u64 = 7_102_839_474;
+        for level in levels() {
+            for len in 0..200 {
+                let buffer: Vec<u8> = (0..len).map(|_| rand_change(&mut seed) as u8).collect();
+                for scale in (0..=255).step_by(7) {
+                    let mut expected = buffer.clone();
+                    expected
+                        .iter_mut()
+                        .for_each(|v| *v = scale_post(*v, u16::from(scale) + 1));
+
+                    let mut actual = buffer.clone();
+                    let done = match level {
+                        Level::Avx2 => unsafe { scale_bytes_avx2(&mut actual, scale) },
+                        _ => unsafe { scale_bytes_sse2(&mut actual, scale) },
+                    };
+                    actual[done..]
+                        .iter_mut()
+                        .for_each(|v| *v = scale_post(*v, u16::from(scale) + 1));
+                    assert_eq!(
+                        expected, actual,
+                        "{:?}, len: {}, scale: {}",
+                        level, len, scale
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blend_matches_scalar() {
+        let mut seed: u64 = 30_041_993_112;
+        for level in levels() {
+            for len in 0..150 {
+                let buffer = rand_pixels(&mut seed, len);
+                let other = ColorRGB::from_color_code(rand_change(&mut seed) as u32);
+                for amount in (0..=255).step_by(5) {
+                    let mut expected = buffer.clone();
+                    blend_scalar(&mut expected, other, amount);
+
+                    let mut actual = buffer.clone();
+                    let done = match level {
+                        Level::Avx2 => unsafe { blend_avx2(&mut actual, other, amount) },
+                        _ => unsafe { blend_sse2(&mut actual, other, amount) },
+                    };
+                    blend_scalar(&mut actual[done..], other, amount);
+                    assert_eq!(
+                        expected, actual,
+                        "{:?}, len: {}, amount: {}",
+                        level, len, amount
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fill_matches_scalar() {
+        let mut seed: u64 = 98_117_230_004;
+        for level in levels() {
+            for len in 0..150 {
+                let buffer = rand_pixels(&mut seed, len);
+                let color = ColorRGB::from_color_code(rand_change(&mut seed) as u32);
+
+                let mut actual = buffer.clone();
+                let done = match level {
+                    Level::Avx2 => unsafe { fill_avx2(&mut actual, color) },
+                    _ => unsafe { fill_sse2(&mut actual, color) },
+                };
+                assert_eq!(&actual[done..], &buffer[done..]);
+                assert!(actual[..done].iter().all(|p| *p == color));
+            }
+        }
+    }
+
+    #[test]
+    fn blur_matches_scalar() {
+        let mut seed: u64 = 661_040_100_923;
+        for level in levels() {
+            for len in 0..150 {
+                let buffer = rand_pixels(&mut seed, len);
+                for blur_amount in (0..=255).step_by(3) {
+                    let keep: u8 = 255 - blur_amount;
+                    let seep: u8 = blur_amount >> 1;
+                    let mut expected = buffer.clone();
+                    blur_scalar(&mut expected, keep, seep, ColorRGB::Black);
+
+                    let mut actual = buffer.clone();
+                    let done = match level {
+                        Level::Avx2 => unsafe { blur_avx2(&mut actual, keep, seep) },
+                        _ => unsafe { blur_sse2(&mut actual, keep, seep) },
+                    };
+                    let carry = match done {
+                        0 => ColorRGB::Black,
+                        _ => {
+                            let mut part = actual[done];
+                            part.scale(seep);
+                            part
+                        }
+                    };
+                    blur_scalar(&mut actual[done..], keep, seep, carry);
+                    assert_eq!(
+                        expected, actual,
+                        "{:?}, len: {}, blur: {}",
+                        level, len, blur_amount
+                    );
+                }
+            }
+        }
+    }
+}
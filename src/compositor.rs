@@ -0,0 +1,182 @@
+//! Flattening stacks of color layers into a single buffer.
+
+use crate::blend::mul;
+use crate::{BlendMode, ColorRGB};
+
+/// A single layer of color to be composited, borrowing a buffer owned by the caller.
+///
+/// Each layer is combined with the layers beneath it by its `mode`, then blended in by its
+/// `opacity`. An optional per-pixel alpha `mask` further scales the opacity of each pixel,
+/// where a mask value of `255` uses the full layer opacity and `0` hides the pixel.
+#[derive(Copy, Clone, Debug)]
+pub struct Layer<'a> {
+    /// The colors of the layer.
+    pub pixels: &'a [ColorRGB],
+    /// How the layer is combined with the colors beneath it.
+    pub mode: BlendMode,
+    /// Opacity of the entire layer.
+    pub opacity: u8,
+    /// Optional per-pixel alpha, scaling `opacity` for each pixel.
+    pub mask: Option<&'a [u8]>,
+}
+
+impl<'a> Layer<'a> {
+    /// Creates a new `Layer` without an alpha mask.
+    #[inline]
+    pub const fn new(pixels: &'a [ColorRGB], mode: BlendMode, opacity: u8) -> Self {
+        Layer {
+            pixels,
+            mode,
+            opacity,
+            mask: None,
+        }
+    }
+
+    /// Sets the per-pixel alpha mask of the layer.
+    #[inline]
+    pub const fn with_mask(mut self, mask: &'a [u8]) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Returns the opacity of the pixel at index `idx`.
+    #[inline(always)]
+    fn opacity_at(&self, idx: usize) -> u8 {
+        match self.mask {
+            None => self.opacity,
+            Some(mask) => match mask.get(idx) {
+                Some(alpha) => mul(self.opacity, *alpha),
+                None => 0,
+            },
+        }
+    }
+
+    /// Combines the layer's pixel at `idx` on top of `base`.
+    #[inline(always)]
+    fn composite(&self, idx: usize, base: ColorRGB) -> ColorRGB {
+        match self.pixels.get(idx) {
+            Some(top) => self.mode.blend(base, *top, self.opacity_at(idx)),
+            None => base,
+        }
+    }
+}
+
+/// Flattens a stack of `Layer`s into a single buffer of colors.
+///
+/// Layers are ordered from bottom to top, and are all applied in a single pass over the
+/// output. The existing colors of the output act as the bottom-most layer, so clear it
+/// beforehand to composite over black.
+///
+/// Layers shorter than the output only cover the start of it, and pixels past the end of
+/// a layer's alpha mask are treated as fully transparent. No memory is allocated, so the
+/// `Compositor` can be used in `no-std` environments.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::{prelude::*, BlendMode, ColorRGB, Compositor, Layer};
+///
+/// let wash = [ColorRGB::new(0, 0, 100); 8];
+/// let mut sparkles = [ColorRGB::Black; 8];
+/// sparkles[3] = ColorRGB::White;
+/// let mask = [255, 255, 255, 255, 0, 0, 0, 0];
+///
+/// let layers = [
+///     Layer::new(&wash, BlendMode::Normal, 255),
+///     Layer::new(&sparkles, BlendMode::Screen, 255).with_mask(&mask),
+/// ];
+///
+/// let mut out = [ColorRGB::Black; 8];
+/// Compositor::new(&layers).flatten(&mut out);
+/// assert_eq!(out[0], ColorRGB::new(0, 0, 100));
+/// assert_eq!(out[3], ColorRGB::White);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Compositor<'l, 'a> {
+    layers: &'l [Layer<'a>],
+}
+
+impl<'l, 'a> Compositor<'l, 'a> {
+    /// Creates a `Compositor` over a stack of layers, ordered from bottom to top.
+    #[inline]
+    pub const fn new(layers: &'l [Layer<'a>]) -> Self {
+        Compositor { layers }
+    }
+
+    /// Returns the stack of layers, ordered from bottom to top.
+    #[inline]
+    pub const fn layers(&self) -> &'l [Layer<'a>] {
+        self.layers
+    }
+
+    /// Composites every layer on top of the colors in `out`.
+    pub fn flatten(&self, out: &mut [ColorRGB]) {
+        out.iter_mut()
+            .enumerate()
+            .for_each(|(i, pixel)| *pixel = self.composite_pixel(i, *pixel));
+    }
+
+    /// Returns the composited color of the pixel at index `idx`, starting from `base`.
+    #[inline]
+    pub fn composite_pixel(&self, idx: usize, base: ColorRGB) -> ColorRGB {
+        self.layers
+            .iter()
+            .fold(base, |base, layer| layer.composite(idx, base))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn matches_sequential_blends() {
+        let mut seed: u32 = 0x1234_5678;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+        let mut a = [ColorRGB::Black; 40];
+        let mut b = [ColorRGB::Black; 40];
+        let mut base = [ColorRGB::Black; 40];
+        a.iter_mut()
+            .for_each(|c| *c = ColorRGB::from_color_code(next()));
+        b.iter_mut()
+            .for_each(|c| *c = ColorRGB::from_color_code(next()));
+        base.iter_mut()
+            .for_each(|c| *c = ColorRGB::from_color_code(next()));
+
+        let layers = [
+            Layer::new(&a, BlendMode::Overlay, 200),
+            Layer::new(&b, BlendMode::Difference, 255),
+        ];
+        let mut out = base;
+        Compositor::new(&layers).flatten(&mut out);
+
+        let mut expected = base;
+        expected.blend_with(&a, BlendMode::Overlay, 200);
+        expected.blend_with(&b, BlendMode::Difference, 255);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn masks_and_short_layers() {
+        let top = [ColorRGB::Red; 4];
+        let mask = [255, 128, 0];
+        let layers = [Layer::new(&top, BlendMode::Normal, 255).with_mask(&mask)];
+
+        let mut out = [ColorRGB::Blue; 6];
+        Compositor::new(&layers).flatten(&mut out);
+        assert_eq!(out[0], ColorRGB::Red);
+        assert_eq!(out[1], ColorRGB::new(128, 0, 127));
+        assert_eq!(&out[2..], &[ColorRGB::Blue; 4]);
+
+        let layers = [Layer::new(&top, BlendMode::Normal, 0)];
+        let mut out = [ColorRGB::Blue; 6];
+        Compositor::new(&layers).flatten(&mut out);
+        assert_eq!(out, [ColorRGB::Blue; 6]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Integration with the `embedded-graphics` crate.
+//!
+//! `ColorRGB` implements `PixelColor` and `RgbColor`, and a `Matrix` is a `DrawTarget`, so
+//! text, shapes, and images from `embedded-graphics` can be drawn straight into a strip.
+
+#[cfg(feature = "no-std")]
+use core::convert::Infallible;
+#[cfg(not(feature = "no-std"))]
+use std::convert::Infallible;
+
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{OriginDimensions, Size};
+use embedded_graphics_core::pixelcolor::raw::{RawData, RawU24};
+use embedded_graphics_core::pixelcolor::{PixelColor, Rgb888, RgbColor};
+use embedded_graphics_core::Pixel;
+
+use crate::matrix::Matrix;
+use crate::ColorRGB;
+
+impl PixelColor for ColorRGB {
+    type Raw = RawU24;
+}
+
+impl RgbColor for ColorRGB {
+    #[inline(always)]
+    fn r(&self) -> u8 {
+        self.r
+    }
+
+    #[inline(always)]
+    fn g(&self) -> u8 {
+        self.g
+    }
+
+    #[inline(always)]
+    fn b(&self) -> u8 {
+        self.b
+    }
+
+    const MAX_R: u8 = 255;
+    const MAX_G: u8 = 255;
+    const MAX_B: u8 = 255;
+
+    const BLACK: Self = ColorRGB::Black;
+    const RED: Self = ColorRGB::Red;
+    const GREEN: Self = ColorRGB::Lime;
+    const BLUE: Self = ColorRGB::Blue;
+    const YELLOW: Self = ColorRGB::Yellow;
+    const MAGENTA: Self = ColorRGB::Magenta;
+    const CYAN: Self = ColorRGB::Cyan;
+    const WHITE: Self = ColorRGB::White;
+}
+
+impl From<RawU24> for ColorRGB {
+    /// Converts from a raw `0xRRGGBB` value, the same as `Rgb888`.
+    #[inline]
+    fn from(raw: RawU24) -> Self {
+        ColorRGB::from_color_code(raw.into_inner())
+    }
+}
+
+impl From<ColorRGB> for RawU24 {
+    /// Converts to a raw `0xRRGGBB` value, the same as `Rgb888`.
+    #[inline]
+    fn from(color: ColorRGB) -> Self {
+        RawU24::new((u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b))
+    }
+}
+
+impl From<Rgb888> for ColorRGB {
+    #[inline]
+    fn from(color: Rgb888) -> Self {
+        ColorRGB::new(color.r(), color.g(), color.b())
+    }
+}
+
+impl From<ColorRGB> for Rgb888 {
+    #[inline]
+    fn from(color: ColorRGB) -> Self {
+        Rgb888::new(color.r, color.g, color.b)
+    }
+}
+
+impl<'a> OriginDimensions for Matrix<'a> {
+    /// Returns the size of the matrix, after the orientation is applied.
+    #[inline]
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+impl<'a> DrawTarget for Matrix<'a> {
+    type Color = ColorRGB;
+    type Error = Infallible;
+
+    /// Sets each pixel of the matrix, following its layout and orientation. Pixels outside of
+    /// the matrix are ignored.
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x >= 0 && point.y >= 0 {
+                self.set(point.x as usize, point.y as usize, color);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::matrix::{Layout, Orientation};
+    use embedded_graphics_core::geometry::Point;
+    use embedded_graphics_core::primitives::Rectangle;
+    use embedded_graphics_core::Drawable;
+
+    #[test]
+    fn colors() {
+        let color = ColorRGB::new(0x12, 0x34, 0x56);
+        assert_eq!(RawU24::from(color).into_inner(), 0x12_34_56);
+        assert_eq!(ColorRGB::from(RawU24::new(0x12_34_56)), color);
+        assert_eq!(ColorRGB::from(Rgb888::from(color)), color);
+        assert_eq!(Rgb888::from(ColorRGB::GREEN), Rgb888::GREEN);
+    }
+
+    #[test]
+    fn draw_target() {
+        let mut leds = [ColorRGB::Black; 4 * 3];
+        let mut matrix = Matrix::new(&mut leds, 4, 3, Layout::Serpentine)
+            .with_orientation(Orientation::Rotate90);
+        assert_eq!(matrix.size(), Size::new(3, 4));
+
+        Pixel(Point::new(2, 3), ColorRGB::Red)
+            .draw(&mut matrix)
+            .unwrap();
+        Pixel(Point::new(-1, 0), ColorRGB::Red)
+            .draw(&mut matrix)
+            .unwrap();
+        Pixel(Point::new(3, 0), ColorRGB::Red)
+            .draw(&mut matrix)
+            .unwrap();
+        assert_eq!(matrix.get(2, 3), Some(ColorRGB::Red));
+        assert_eq!(
+            matrix
+                .pixels()
+                .iter()
+                .filter(|c| **c == ColorRGB::Red)
+                .count(),
+            1
+        );
+
+        let area = Rectangle::new(Point::new(1, 1), Size::new(5, 2));
+        matrix.fill_solid(&area, ColorRGB::Blue).unwrap();
+        assert_eq!(
+            matrix
+                .pixels()
+                .iter()
+                .filter(|c| **c == ColorRGB::Blue)
+                .count(),
+            4
+        );
+        matrix.clear(ColorRGB::White).unwrap();
+        assert!(matrix.pixels().iter().all(|c| *c == ColorRGB::White));
+    }
+}
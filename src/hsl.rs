@@ -0,0 +1,313 @@
+//! Contains the HSL (hue, saturation, lightness) representation of a color.
+
+#[cfg(feature = "no-std")]
+use core::fmt;
+#[cfg(not(feature = "no-std"))]
+use std::fmt;
+
+use crate::hsv::spectrum_from_extremes;
+use crate::{ColorRGB, HSV};
+
+/// Represents a color encoded in `(hue, saturation, lightness)` format.
+///
+/// This is the color model used by CSS and most web color pickers. Like `HSV`, each component
+/// is a `u8`, with the hue going once around the color wheel from `0` to `255`. Unlike `HSV`,
+/// a lightness of `255` is always white, while fully saturated colors sit at a lightness of
+/// `128`.
+///
+/// The hue follows a traditional mathematical color wheel, with red, green, and blue evenly
+/// spaced, matching `HSV::to_rgb_spectrum_full()` rather than the default rainbow of `HSV`.
+/// Converting a `HSV` with `HSL::from()` keeps the color it renders as, so filling a buffer
+/// of `HSL`s gives the same colors as filling a buffer of `ColorRGB`s.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::{ColorRGB, HSL};
+///
+/// let red = HSL::new(0, 255, 128);
+/// assert_eq!(ColorRGB::from(red), ColorRGB::Red);
+///
+/// let pink = HSL::from(ColorRGB::new(255, 128, 128));
+/// assert_eq!(pink, HSL::new(0, 255, 192));
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HSL {
+    pub h: u8,
+    pub s: u8,
+    pub l: u8,
+}
+
+impl HSL {
+    /// Blank `HSL` object where all values are initialized to zero.
+    pub const BLANK: HSL = HSL { h: 0, s: 0, l: 0 };
+
+    /// Create a new `HSL` object.
+    #[inline(always)]
+    pub const fn new(h: u8, s: u8, l: u8) -> Self {
+        HSL { h, s, l }
+    }
+
+    /// Grabs the hue component of the `HSL`.
+    #[inline(always)]
+    pub fn hue(self) -> u8 {
+        self.h
+    }
+    /// Grabs the saturation component of the `HSL`.
+    #[inline(always)]
+    pub fn saturation(self) -> u8 {
+        self.s
+    }
+    /// Grabs the lightness component of the `HSL`.
+    #[inline(always)]
+    pub fn lightness(self) -> u8 {
+        self.l
+    }
+
+    /// Converts hue, saturation, and lightness to a `ColorRGB`.
+    pub fn to_rgb(self) -> ColorRGB {
+        let (max, min) = self.extremes();
+        spectrum_from_extremes(self.h, max, min)
+    }
+
+    /// Converts a `ColorRGB` to hue, saturation, and lightness.
+    pub fn from_rgb(rgb: ColorRGB) -> Self {
+        let max: u32 = u32::from(rgb.r.max(rgb.g).max(rgb.b));
+        let min: u32 = u32::from(rgb.r.min(rgb.g).min(rgb.b));
+        let hue: u8 = HSV::from_rgb_spectrum(rgb).h;
+        HSL::from_extremes(hue, max * 255, min * 255)
+    }
+
+    /// Converts to a `HSV` with the same hue, where the `HSV` is read on the mathematical
+    /// color wheel of `HSV::to_rgb_spectrum_full()`.
+    ///
+    /// Rendering the `HSV` as a rainbow (as `ColorRGB::from()` does) will give a different
+    /// color, since the rainbow places its hues differently.
+    pub fn to_hsv_spectrum(self) -> HSV {
+        let (max, min) = self.extremes();
+        let sat: u32 = match max {
+            0 => 0,
+            max => ((max - min) * 255 + max / 2) / max,
+        };
+        HSV::new(self.h, sat as u8, ((max + 127) / 255) as u8)
+    }
+
+    /// Converts from a `HSV` with the same hue, where the `HSV` is read on the mathematical
+    /// color wheel of `HSV::to_rgb_spectrum_full()`.
+    ///
+    /// To keep the color a `HSV` renders as a rainbow, use `HSL::from()` instead.
+    pub fn from_hsv_spectrum(hsv: HSV) -> Self {
+        let (s, v) = (u32::from(hsv.s), u32::from(hsv.v));
+        HSL::from_extremes(hsv.h, v * 255, v * (255 - s))
+    }
+
+    /// Returns the largest and smallest RGB components, out of `255 * 255`.
+    ///
+    /// A lightness of `128` is exactly half way, so that fully saturated colors reach both
+    /// `0` and `255`.
+    fn extremes(self) -> (u32, u32) {
+        let (s, l) = (u32::from(self.s), u32::from(self.l));
+        let sum: u32 = if l <= 128 {
+            (l * FULL + 64) / 128
+        } else {
+            FULL + ((l - 128) * FULL + 63) / 127
+        };
+        let c: u32 = (range(sum) * s + 127) / 255;
+        ((sum + c).div_ceil(2), (sum - c).div_ceil(2))
+    }
+
+    /// Creates a `HSL` from the largest and smallest RGB components, out of `255 * 255`.
+    fn from_extremes(hue: u8, max: u32, min: u32) -> Self {
+        let sum: u32 = max + min;
+        let lightness: u32 = if sum <= FULL {
+            (sum * 128 + FULL / 2) / FULL
+        } else {
+            128 + ((sum - FULL) * 127 + FULL / 2) / FULL
+        };
+        let sat: u32 = match max - min {
+            0 => 0,
+            chroma => (chroma * 255 + range(sum) / 2) / range(sum),
+        };
+        HSL::new(hue, sat.min(255) as u8, lightness as u8)
+    }
+}
+
+/// Full brightness of a component, out of `255 * 255`.
+const FULL: u32 = 255 * 255;
+
+/// Largest possible chroma for a sum of the largest and smallest components.
+#[inline(always)]
+fn range(sum: u32) -> u32 {
+    FULL + sum.min(FULL) - sum.max(FULL)
+}
+
+impl fmt::Display for HSL {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.h, self.s, self.l)
+    }
+}
+
+impl From<(u8, u8, u8)> for HSL {
+    #[inline(always)]
+    fn from(other: (u8, u8, u8)) -> Self {
+        Self::new(other.0, other.1, other.2)
+    }
+}
+
+impl From<[u8; 3]> for HSL {
+    #[inline(always)]
+    fn from(other: [u8; 3]) -> Self {
+        Self::new(other[0], other[1], other[2])
+    }
+}
+
+impl From<HSL> for ColorRGB {
+    #[inline(always)]
+    fn from(hsl: HSL) -> Self {
+        hsl.to_rgb()
+    }
+}
+
+impl From<ColorRGB> for HSL {
+    #[inline(always)]
+    fn from(rgb: ColorRGB) -> Self {
+        HSL::from_rgb(rgb)
+    }
+}
+
+/// Converts the rainbow color of a `HSV`, so that both render as the same `ColorRGB`.
+impl From<HSV> for HSL {
+    #[inline]
+    fn from(hsv: HSV) -> Self {
+        HSL::from_rgb(hsv.to_rgb_rainbow())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::{ColorRGB, ColorRGBA, GradientDirection, HSL, HSV};
+
+    #[test]
+    fn hsl2rgb_reference() {
+        // CSS colors, with the hue in degrees converted to the nearest of 256 steps.
+        let table = [
+            ((0, 100, 50), (255, 0, 0)),
+            ((120, 100, 50), (0, 255, 0)),
+            ((240, 100, 25), (0, 0, 128)),
+            ((60, 100, 50), (255, 255, 0)),
+            ((210, 50, 40), (51, 102, 153)),
+            ((300, 50, 50), (191, 64, 191)),
+            ((30, 0, 100), (255, 255, 255)),
+            ((0, 0, 50), (128, 128, 128)),
+        ];
+        for &((deg, s, l), (r, g, b)) in table.iter() {
+            let hsl = HSL::new(
+                ((deg * 256 + 180) / 360 % 256) as u8,
+                ((s * 255 + 50) / 100) as u8,
+                ((l * 255 + 50) / 100) as u8,
+            );
+            let rgb = ColorRGB::from(hsl);
+            for (actual, expected) in [rgb.r, rgb.g, rgb.b].iter().zip([r, g, b].iter()) {
+                assert!((i16::from(*actual) - expected).abs() <= 2, "{:?}", rgb);
+            }
+        }
+    }
+
+    #[test]
+    fn rgb_round_trips() {
+        for code in (0..0xFF_FFFFu32).step_by(0x1357) {
+            let color = ColorRGB::from_color_code(code);
+            let back = ColorRGB::from(HSL::from(color));
+            // With only 256 hues, saturated colors can be off by a couple of steps.
+            for i in 0..3 {
+                assert!((i16::from(back[i]) - i16::from(color[i])).abs() <= 4);
+            }
+        }
+        assert_eq!(HSL::from(ColorRGB::White), HSL::new(0, 0, 255));
+        assert_eq!(HSL::from(ColorRGB::Black), HSL::new(0, 0, 0));
+        assert_eq!(HSL::from(ColorRGB::Blue), HSL::new(171, 255, 128));
+    }
+
+    #[test]
+    fn hsv_spectrum_conversions() {
+        assert_eq!(
+            HSL::new(10, 255, 128).to_hsv_spectrum(),
+            HSV::new(10, 255, 255)
+        );
+        assert_eq!(HSL::new(10, 0, 255).to_hsv_spectrum(), HSV::new(10, 0, 255));
+        assert_eq!(
+            HSL::from_hsv_spectrum(HSV::new(10, 255, 255)),
+            HSL::new(10, 255, 128)
+        );
+        assert_eq!(
+            HSL::from_hsv_spectrum(HSV::new(10, 0, 0)),
+            HSL::new(10, 0, 0)
+        );
+        for s in (0..=255).step_by(17) {
+            for l in (0..=255).step_by(17) {
+                let hsl = HSL::new(100, s as u8, l as u8);
+                let color = hsl.to_rgb();
+                let via_hsv = hsl.to_hsv_spectrum().to_rgb_spectrum_full();
+                for i in 0..3 {
+                    assert!((i16::from(via_hsv[i]) - i16::from(color[i])).abs() <= 2);
+                }
+                assert_eq!(HSL::from_hsv_spectrum(hsl.to_hsv_spectrum()).h, 100);
+            }
+        }
+    }
+
+    #[test]
+    fn hsv_keeps_rainbow_color() {
+        // Rainbow yellow, rather than the chartreuse `(128, 255, 0)` at the same spectrum hue.
+        let yellow = ColorRGB::from(HSL::from(HSV::new(64, 255, 255)));
+        assert!(yellow.r >= 169 && yellow.g >= 169 && yellow.b == 0);
+        for h in 0..=255 {
+            for s in (0..=255).step_by(17) {
+                for v in (0..=255).step_by(17) {
+                    let hsv = HSV::new(h as u8, s as u8, v as u8);
+                    let (color, via_hsl) = (ColorRGB::from(hsv), ColorRGB::from(HSL::from(hsv)));
+                    // Rounding to one of 256 hues moves saturated colors by up to 3 steps.
+                    for i in 0..3 {
+                        assert!(
+                            (i16::from(via_hsl[i]) - i16::from(color[i])).abs() <= 3,
+                            "{:?}: {:?} != {:?}",
+                            hsv,
+                            via_hsl,
+                            color
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gradients() {
+        let close = |hsl: &[HSL; 4], rgb: &[ColorRGB; 4]| {
+            for (a, b) in hsl.iter().zip(rgb.iter()) {
+                let a = ColorRGB::from(*a);
+                for i in 0..3 {
+                    assert!((i16::from(a[i]) - i16::from(b[i])).abs() <= 2);
+                }
+            }
+        };
+        let (start, end) = (HSV::new(0, 255, 255), HSV::new(128, 255, 255));
+        let (mut out, mut rgb) = ([HSL::BLANK; 4], [ColorRGB::Black; 4]);
+        out.gradient_fill(start, end, GradientDirection::Forward);
+        rgb.gradient_fill(start, end, GradientDirection::Forward);
+        close(&out, &rgb);
+
+        out.gradient_fill_rgb(ColorRGB::Black, ColorRGB::White);
+        assert_eq!(out[2], HSL::new(0, 0, 128));
+
+        out.rainbow_fill(0, 32 << 8);
+        rgb.rainbow_fill(0, 32 << 8);
+        close(&out, &rgb);
+
+        let mut out = [ColorRGBA::TRANSPARENT; 2];
+        out[1] = HSL::new(0, 255, 128).into();
+        assert_eq!(out[1], ColorRGBA::new(255, 0, 0, 255));
+    }
+}
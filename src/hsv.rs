@@ -19,11 +19,56 @@ fn hue_to_full_rgb(hue: u8) -> ColorRGB {
     hsv_inner::hue2rgb_rainbow(hue)
 }
 
+/// Applies saturation and value to the full brightness and saturation rainbow color of a hue.
+#[inline(always)]
+fn rainbow_sat_val<F: FnOnce() -> ColorRGB>(full_rgb: F, sat: u8, val: u8) -> ColorRGB {
+    let mut rgb: ColorRGB = match (sat, val) {
+        (0, _) => return mk_rgb!(255, 255, 255),
+        (_, 0) => return mk_rgb!(0, 0, 0),
+        _ => full_rgb(),
+    };
+
+    if sat != 255 {
+        rgb.scale(sat);
+        let desat: u8 = 255 - sat;
+        let brightness_floor: u8 = desat.dim_raw();
+        rgb += brightness_floor;
+    }
+
+    if val != 255 {
+        rgb.scale(val);
+    }
+
+    rgb
+}
+
+/// Converts a hue on the mathematical color wheel to RGB, given the largest and smallest
+/// components out of `255 * 255`.
+pub(crate) fn spectrum_from_extremes(hue: u8, max: u32, min: u32) -> ColorRGB {
+    let c: u32 = max - min;
+
+    // Position around the wheel, where each of the six sections is 256 steps.
+    let pos: u32 = u32::from(hue) * 6;
+    let fract: u32 = pos % 256;
+    let (rising, falling) = (c * fract / 256, c * (256 - fract) / 256);
+    let (r, g, b) = match pos / 256 {
+        0 => (c, rising, 0),
+        1 => (falling, c, 0),
+        2 => (0, c, rising),
+        3 => (0, falling, c),
+        4 => (rising, 0, c),
+        _ => (c, 0, falling),
+    };
+    let channel = |v: u32| ((v + min + 127) / 255) as u8;
+    mk_rgb!(channel(r), channel(g), channel(b))
+}
+
 /// Represents a color encoded in `(hue, saturation, value)` format.
 ///
 /// This structure is useful for a more human-centered approach to thinking
 /// about color.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HSV {
     pub h: u8,
     pub s: u8,
@@ -58,19 +103,53 @@ impl HSV {
 
     /// Converts hue, saturation, and value to a `ColorRGB` using a visually balanced rainbow.
     pub fn to_rgb_rainbow(self) -> ColorRGB {
-        let (hue, sat, val) = (self.h, self.s, self.v);
+        rainbow_sat_val(|| hue_to_full_rgb(self.h), self.s, self.v)
+    }
 
-        let mut rgb: ColorRGB = match (sat, val) {
-            (0, _) => return mk_rgb!(255, 255, 255),
-            (_, 0) => return mk_rgb!(0, 0, 0),
-            _ => hue_to_full_rgb(hue),
-        };
+    /// Converts a `HSV` to a `ColorRGB` using a traditional Mathematical rainbow.
+    ///
+    /// The value is scaled down to at most `191`, so the result is never brighter than three
+    /// quarters of full brightness. `to_rgb_spectrum_full()` reaches all the way to `255`.
+    pub fn to_rgb_spectrum(self) -> ColorRGB {
+        let mut hsv = self;
+        hsv.v = scale_u8(hsv.v, 191);
+        unsafe { hsv.to_rgb_raw() }
+    }
+
+    /// Converts a `HSV` to a `ColorRGB` around a traditional Mathematical color wheel, with
+    /// red, green, and blue evenly spaced, using the full range of brightness.
+    ///
+    /// This is computed exactly, and undoes the conversion of a `ColorRGB` to a `HSV` (in
+    /// `FromStr`, for example) to within the precision of the 256 hues.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{ColorRGB, HSV};
+    ///
+    /// assert_eq!(HSV::new(0, 255, 255).to_rgb_spectrum_full(), ColorRGB::Red);
+    /// assert_eq!(HSV::new(128, 255, 255).to_rgb_spectrum_full(), ColorRGB::Cyan);
+    /// assert_eq!(HSV::new(0, 128, 255).to_rgb_spectrum_full(), ColorRGB::new(255, 127, 127));
+    /// ```
+    pub fn to_rgb_spectrum_full(self) -> ColorRGB {
+        let (s, v) = (u32::from(self.s), u32::from(self.v));
+        spectrum_from_extremes(self.h, v * 255, v * (255 - s))
+    }
+
+    /// Converts a `HSV` to a `ColorRGB` around a traditional Mathematical color wheel, using
+    /// the full range of brightness.
+    ///
+    /// This looks up the fully saturated color of the hue in a table before scaling it, which
+    /// is faster than `to_rgb_spectrum_full()` but may be off by a step or two in each
+    /// component. With the `low-mem` feature, the table is replaced by computing the color
+    /// instead.
+    pub fn to_rgb_spectrum_fast(self) -> ColorRGB {
+        let (sat, val) = (self.s, self.v);
+        let mut rgb: ColorRGB = hsv_inner::hue2rgb_spectrum(self.h);
 
         if sat != 255 {
             rgb.scale(sat);
-            let desat: u8 = 255 - sat;
-            let brightness_floor: u8 = desat.dim_raw();
-            rgb += brightness_floor;
+            rgb += 255 - sat;
         }
 
         if val != 255 {
@@ -80,18 +159,27 @@ impl HSV {
         rgb
     }
 
-    /// Converts a `HSV` to a `ColorRGB` using a traditional Mathematical rainbow.
-    pub fn to_rgb_spectrum(self) -> ColorRGB {
-        let mut hsv = self;
-        hsv.v = scale_u8(hsv.v, 191);
-        unsafe { hsv.to_rgb_raw() }
+    /// Converts a `ColorRGB` to a `HSV` around a traditional Mathematical color wheel, with
+    /// red, green, and blue evenly spaced.
+    pub(crate) fn from_rgb_spectrum(rgb: ColorRGB) -> Self {
+        let max: u32 = u32::from(rgb.r.max(rgb.g).max(rgb.b));
+        let chroma: u32 = max - u32::from(rgb.r.min(rgb.g).min(rgb.b));
+        match rgb.spectrum_position() {
+            Some(pos) => {
+                let hue: u8 = ((pos + 3) / 6 % 256) as u8;
+                let sat: u8 = ((chroma * 255 + max / 2) / max) as u8;
+                HSV::new(hue, sat, max as u8)
+            }
+            None => HSV::new(0, 0, max as u8),
+        }
     }
 
     /// Converts a `HSV` to a `ColorRGB` using a traditional Mathematical rainbow.
     ///
     /// # Safety
     ///
-    /// Value can only be up to 191, or else undefined behavior will follow.
+    /// Value can only be up to 191, or else undefined behavior will follow. For a safe
+    /// conversion reaching full brightness, use `to_rgb_spectrum_full()`.
     pub unsafe fn to_rgb_raw(self) -> ColorRGB {
         // Taken directly from FastLED, credit goes to them for this
         debug_assert!(self.v <= 191);
@@ -150,7 +238,7 @@ impl HSV {
 // Compacted function for low memory usage
 #[cfg(feature = "low-mem")]
 mod hsv_inner {
-    use crate::math::scale::scale8;
+    use crate::math::scale_u8 as scale8;
     use crate::ColorRGB;
     #[cfg(feature = "no-std")]
     use core::hint::unreachable_unchecked;
@@ -182,6 +270,11 @@ mod hsv_inner {
         };
         rgb
     }
+
+    #[inline(always)]
+    pub fn hue2rgb_spectrum(hue: u8) -> ColorRGB {
+        super::spectrum_from_extremes(hue, 255 * 255, 0)
+    }
 }
 
 #[cfg(not(feature = "low-mem"))]
@@ -193,6 +286,11 @@ mod hsv_inner {
         unsafe { *HSV_2_RGB_RAINBOW.get_unchecked(hue as usize) }
     }
 
+    #[inline(always)]
+    pub fn hue2rgb_spectrum(hue: u8) -> ColorRGB {
+        unsafe { *HSV_2_RGB_SPECTRUM.get_unchecked(hue as usize) }
+    }
+
     static HSV_2_RGB_RAINBOW: [ColorRGB; 256] = [
         mk_rgb!(255, 0, 0),
         mk_rgb!(253, 2, 0),
@@ -451,6 +549,265 @@ mod hsv_inner {
         mk_rgb!(250, 0, 5),
         mk_rgb!(253, 0, 2),
     ];
+
+    static HSV_2_RGB_SPECTRUM: [ColorRGB; 256] = [
+        mk_rgb!(255, 0, 0),
+        mk_rgb!(255, 6, 0),
+        mk_rgb!(255, 12, 0),
+        mk_rgb!(255, 18, 0),
+        mk_rgb!(255, 24, 0),
+        mk_rgb!(255, 30, 0),
+        mk_rgb!(255, 36, 0),
+        mk_rgb!(255, 42, 0),
+        mk_rgb!(255, 48, 0),
+        mk_rgb!(255, 54, 0),
+        mk_rgb!(255, 60, 0),
+        mk_rgb!(255, 66, 0),
+        mk_rgb!(255, 72, 0),
+        mk_rgb!(255, 78, 0),
+        mk_rgb!(255, 84, 0),
+        mk_rgb!(255, 90, 0),
+        mk_rgb!(255, 96, 0),
+        mk_rgb!(255, 102, 0),
+        mk_rgb!(255, 108, 0),
+        mk_rgb!(255, 114, 0),
+        mk_rgb!(255, 120, 0),
+        mk_rgb!(255, 126, 0),
+        mk_rgb!(255, 131, 0),
+        mk_rgb!(255, 137, 0),
+        mk_rgb!(255, 143, 0),
+        mk_rgb!(255, 149, 0),
+        mk_rgb!(255, 155, 0),
+        mk_rgb!(255, 161, 0),
+        mk_rgb!(255, 167, 0),
+        mk_rgb!(255, 173, 0),
+        mk_rgb!(255, 179, 0),
+        mk_rgb!(255, 185, 0),
+        mk_rgb!(255, 191, 0),
+        mk_rgb!(255, 197, 0),
+        mk_rgb!(255, 203, 0),
+        mk_rgb!(255, 209, 0),
+        mk_rgb!(255, 215, 0),
+        mk_rgb!(255, 221, 0),
+        mk_rgb!(255, 227, 0),
+        mk_rgb!(255, 233, 0),
+        mk_rgb!(255, 239, 0),
+        mk_rgb!(255, 245, 0),
+        mk_rgb!(255, 251, 0),
+        mk_rgb!(253, 255, 0),
+        mk_rgb!(247, 255, 0),
+        mk_rgb!(241, 255, 0),
+        mk_rgb!(235, 255, 0),
+        mk_rgb!(229, 255, 0),
+        mk_rgb!(223, 255, 0),
+        mk_rgb!(217, 255, 0),
+        mk_rgb!(211, 255, 0),
+        mk_rgb!(205, 255, 0),
+        mk_rgb!(199, 255, 0),
+        mk_rgb!(193, 255, 0),
+        mk_rgb!(187, 255, 0),
+        mk_rgb!(181, 255, 0),
+        mk_rgb!(175, 255, 0),
+        mk_rgb!(169, 255, 0),
+        mk_rgb!(163, 255, 0),
+        mk_rgb!(157, 255, 0),
+        mk_rgb!(151, 255, 0),
+        mk_rgb!(145, 255, 0),
+        mk_rgb!(139, 255, 0),
+        mk_rgb!(133, 255, 0),
+        mk_rgb!(127, 255, 0),
+        mk_rgb!(122, 255, 0),
+        mk_rgb!(116, 255, 0),
+        mk_rgb!(110, 255, 0),
+        mk_rgb!(104, 255, 0),
+        mk_rgb!(98, 255, 0),
+        mk_rgb!(92, 255, 0),
+        mk_rgb!(86, 255, 0),
+        mk_rgb!(80, 255, 0),
+        mk_rgb!(74, 255, 0),
+        mk_rgb!(68, 255, 0),
+        mk_rgb!(62, 255, 0),
+        mk_rgb!(56, 255, 0),
+        mk_rgb!(50, 255, 0),
+        mk_rgb!(44, 255, 0),
+        mk_rgb!(38, 255, 0),
+        mk_rgb!(32, 255, 0),
+        mk_rgb!(26, 255, 0),
+        mk_rgb!(20, 255, 0),
+        mk_rgb!(14, 255, 0),
+        mk_rgb!(8, 255, 0),
+        mk_rgb!(2, 255, 0),
+        mk_rgb!(0, 255, 4),
+        mk_rgb!(0, 255, 10),
+        mk_rgb!(0, 255, 16),
+        mk_rgb!(0, 255, 22),
+        mk_rgb!(0, 255, 28),
+        mk_rgb!(0, 255, 34),
+        mk_rgb!(0, 255, 40),
+        mk_rgb!(0, 255, 46),
+        mk_rgb!(0, 255, 52),
+        mk_rgb!(0, 255, 58),
+        mk_rgb!(0, 255, 64),
+        mk_rgb!(0, 255, 70),
+        mk_rgb!(0, 255, 76),
+        mk_rgb!(0, 255, 82),
+        mk_rgb!(0, 255, 88),
+        mk_rgb!(0, 255, 94),
+        mk_rgb!(0, 255, 100),
+        mk_rgb!(0, 255, 106),
+        mk_rgb!(0, 255, 112),
+        mk_rgb!(0, 255, 118),
+        mk_rgb!(0, 255, 124),
+        mk_rgb!(0, 255, 129),
+        mk_rgb!(0, 255, 135),
+        mk_rgb!(0, 255, 141),
+        mk_rgb!(0, 255, 147),
+        mk_rgb!(0, 255, 153),
+        mk_rgb!(0, 255, 159),
+        mk_rgb!(0, 255, 165),
+        mk_rgb!(0, 255, 171),
+        mk_rgb!(0, 255, 177),
+        mk_rgb!(0, 255, 183),
+        mk_rgb!(0, 255, 189),
+        mk_rgb!(0, 255, 195),
+        mk_rgb!(0, 255, 201),
+        mk_rgb!(0, 255, 207),
+        mk_rgb!(0, 255, 213),
+        mk_rgb!(0, 255, 219),
+        mk_rgb!(0, 255, 225),
+        mk_rgb!(0, 255, 231),
+        mk_rgb!(0, 255, 237),
+        mk_rgb!(0, 255, 243),
+        mk_rgb!(0, 255, 249),
+        mk_rgb!(0, 255, 255),
+        mk_rgb!(0, 249, 255),
+        mk_rgb!(0, 243, 255),
+        mk_rgb!(0, 237, 255),
+        mk_rgb!(0, 231, 255),
+        mk_rgb!(0, 225, 255),
+        mk_rgb!(0, 219, 255),
+        mk_rgb!(0, 213, 255),
+        mk_rgb!(0, 207, 255),
+        mk_rgb!(0, 201, 255),
+        mk_rgb!(0, 195, 255),
+        mk_rgb!(0, 189, 255),
+        mk_rgb!(0, 183, 255),
+        mk_rgb!(0, 177, 255),
+        mk_rgb!(0, 171, 255),
+        mk_rgb!(0, 165, 255),
+        mk_rgb!(0, 159, 255),
+        mk_rgb!(0, 153, 255),
+        mk_rgb!(0, 147, 255),
+        mk_rgb!(0, 141, 255),
+        mk_rgb!(0, 135, 255),
+        mk_rgb!(0, 129, 255),
+        mk_rgb!(0, 124, 255),
+        mk_rgb!(0, 118, 255),
+        mk_rgb!(0, 112, 255),
+        mk_rgb!(0, 106, 255),
+        mk_rgb!(0, 100, 255),
+        mk_rgb!(0, 94, 255),
+        mk_rgb!(0, 88, 255),
+        mk_rgb!(0, 82, 255),
+        mk_rgb!(0, 76, 255),
+        mk_rgb!(0, 70, 255),
+        mk_rgb!(0, 64, 255),
+        mk_rgb!(0, 58, 255),
+        mk_rgb!(0, 52, 255),
+        mk_rgb!(0, 46, 255),
+        mk_rgb!(0, 40, 255),
+        mk_rgb!(0, 34, 255),
+        mk_rgb!(0, 28, 255),
+        mk_rgb!(0, 22, 255),
+        mk_rgb!(0, 16, 255),
+        mk_rgb!(0, 10, 255),
+        mk_rgb!(0, 4, 255),
+        mk_rgb!(2, 0, 255),
+        mk_rgb!(8, 0, 255),
+        mk_rgb!(14, 0, 255),
+        mk_rgb!(20, 0, 255),
+        mk_rgb!(26, 0, 255),
+        mk_rgb!(32, 0, 255),
+        mk_rgb!(38, 0, 255),
+        mk_rgb!(44, 0, 255),
+        mk_rgb!(50, 0, 255),
+        mk_rgb!(56, 0, 255),
+        mk_rgb!(62, 0, 255),
+        mk_rgb!(68, 0, 255),
+        mk_rgb!(74, 0, 255),
+        mk_rgb!(80, 0, 255),
+        mk_rgb!(86, 0, 255),
+        mk_rgb!(92, 0, 255),
+        mk_rgb!(98, 0, 255),
+        mk_rgb!(104, 0, 255),
+        mk_rgb!(110, 0, 255),
+        mk_rgb!(116, 0, 255),
+        mk_rgb!(122, 0, 255),
+        mk_rgb!(127, 0, 255),
+        mk_rgb!(133, 0, 255),
+        mk_rgb!(139, 0, 255),
+        mk_rgb!(145, 0, 255),
+        mk_rgb!(151, 0, 255),
+        mk_rgb!(157, 0, 255),
+        mk_rgb!(163, 0, 255),
+        mk_rgb!(169, 0, 255),
+        mk_rgb!(175, 0, 255),
+        mk_rgb!(181, 0, 255),
+        mk_rgb!(187, 0, 255),
+        mk_rgb!(193, 0, 255),
+        mk_rgb!(199, 0, 255),
+        mk_rgb!(205, 0, 255),
+        mk_rgb!(211, 0, 255),
+        mk_rgb!(217, 0, 255),
+        mk_rgb!(223, 0, 255),
+        mk_rgb!(229, 0, 255),
+        mk_rgb!(235, 0, 255),
+        mk_rgb!(241, 0, 255),
+        mk_rgb!(247, 0, 255),
+        mk_rgb!(253, 0, 255),
+        mk_rgb!(255, 0, 251),
+        mk_rgb!(255, 0, 245),
+        mk_rgb!(255, 0, 239),
+        mk_rgb!(255, 0, 233),
+        mk_rgb!(255, 0, 227),
+        mk_rgb!(255, 0, 221),
+        mk_rgb!(255, 0, 215),
+        mk_rgb!(255, 0, 209),
+        mk_rgb!(255, 0, 203),
+        mk_rgb!(255, 0, 197),
+        mk_rgb!(255, 0, 191),
+        mk_rgb!(255, 0, 185),
+        mk_rgb!(255, 0, 179),
+        mk_rgb!(255, 0, 173),
+        mk_rgb!(255, 0, 167),
+        mk_rgb!(255, 0, 161),
+        mk_rgb!(255, 0, 155),
+        mk_rgb!(255, 0, 149),
+        mk_rgb!(255, 0, 143),
+        mk_rgb!(255, 0, 137),
+        mk_rgb!(255, 0, 131),
+        mk_rgb!(255, 0, 126),
+        mk_rgb!(255, 0, 120),
+        mk_rgb!(255, 0, 114),
+        mk_rgb!(255, 0, 108),
+        mk_rgb!(255, 0, 102),
+        mk_rgb!(255, 0, 96),
+        mk_rgb!(255, 0, 90),
+        mk_rgb!(255, 0, 84),
+        mk_rgb!(255, 0, 78),
+        mk_rgb!(255, 0, 72),
+        mk_rgb!(255, 0, 66),
+        mk_rgb!(255, 0, 60),
+        mk_rgb!(255, 0, 54),
+        mk_rgb!(255, 0, 48),
+        mk_rgb!(255, 0, 42),
+        mk_rgb!(255, 0, 36),
+        mk_rgb!(255, 0, 30),
+        mk_rgb!(255, 0, 24),
+        mk_rgb!(255, 0, 18),
+        mk_rgb!(255, 0, 12),
+        mk_rgb!(255, 0, 6),
+    ];
 }
 
 impl fmt::Display for HSV {
@@ -473,9 +830,107 @@ impl From<[u8; 3]> for HSV {
     }
 }
 
+/// Represents a color in `(hue, saturation, value)` format, with a 16 bit hue.
+///
+/// The high byte of the hue is the same as the hue of a `HSV`, while the low byte moves
+/// smoothly between each of those 256 hues. This makes slow rainbows across long strips (or
+/// long periods of time) change without visible steps.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::{ColorRGB, HSV, HSV16};
+///
+/// let lower = ColorRGB::from(HSV::new(0x40, 255, 255));
+/// let upper = ColorRGB::from(HSV::new(0x41, 255, 255));
+/// assert_eq!(ColorRGB::from(HSV16::new(0x4000, 255, 255)), lower);
+///
+/// // Halfway between hues 0x40 and 0x41.
+/// let between = ColorRGB::from(HSV16::new(0x4080, 255, 255));
+/// assert!(lower.g <= between.g && between.g <= upper.g);
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HSV16 {
+    pub h: u16,
+    pub s: u8,
+    pub v: u8,
+}
+
+impl HSV16 {
+    /// Blank `HSV16` object where all values are initialized to zero.
+    pub const BLANK: HSV16 = HSV16 { h: 0, s: 0, v: 0 };
+
+    /// Create a new `HSV16` object.
+    #[inline(always)]
+    pub const fn new(h: u16, s: u8, v: u8) -> Self {
+        HSV16 { h, s, v }
+    }
+
+    /// Grabs the hue component of the `HSV16`.
+    #[inline(always)]
+    pub fn hue(self) -> u16 {
+        self.h
+    }
+    /// Grabs the saturation component of the `HSV16`.
+    #[inline(always)]
+    pub fn saturation(self) -> u8 {
+        self.s
+    }
+    /// Grabs the value component of the `HSV16`.
+    #[inline(always)]
+    pub fn value(self) -> u8 {
+        self.v
+    }
+
+    /// Converts hue, saturation, and value to a `ColorRGB` using a visually balanced rainbow,
+    /// interpolating between the 256 hues of `HSV::to_rgb_rainbow()`.
+    pub fn to_rgb_rainbow(self) -> ColorRGB {
+        let full_rgb = || {
+            let hue: u8 = (self.h >> 8) as u8;
+            let fract: i16 = (self.h & 0xFF) as i16;
+            let lower: ColorRGB = hue_to_full_rgb(hue);
+            if fract == 0 {
+                return lower;
+            }
+            let upper: ColorRGB = hue_to_full_rgb(hue.wrapping_add(1));
+            let lerp =
+                |a: u8, b: u8| (i16::from(a) + (i16::from(b) - i16::from(a)) * fract / 256) as u8;
+            mk_rgb!(
+                lerp(lower.r, upper.r),
+                lerp(lower.g, upper.g),
+                lerp(lower.b, upper.b)
+            )
+        };
+        rainbow_sat_val(full_rgb, self.s, self.v)
+    }
+}
+
+impl fmt::Display for HSV16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.h, self.s, self.v)
+    }
+}
+
+impl From<HSV> for HSV16 {
+    #[inline(always)]
+    fn from(hsv: HSV) -> Self {
+        HSV16::new(u16::from(hsv.h) << 8, hsv.s, hsv.v)
+    }
+}
+
+impl From<HSV16> for HSV {
+    /// Converts to a `HSV`, dropping the low byte of the hue.
+    #[inline(always)]
+    fn from(hsv: HSV16) -> Self {
+        HSV::new((hsv.h >> 8) as u8, hsv.s, hsv.v)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{ColorRGB, HSV};
+    use crate::prelude::*;
+    use crate::{ColorRGB, HSV, HSV16};
 
     #[test]
     fn hsv2rgb_rainbow_6h() {
@@ -516,4 +971,109 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn hsv16_matches_hsv() {
+        for h in (0..=255).step_by(5) {
+            for s in (0..=255).step_by(15) {
+                for v in (0..=255).step_by(15) {
+                    let hsv = HSV::new(h as u8, s as u8, v as u8);
+                    assert_eq!(ColorRGB::from(HSV16::from(hsv)), ColorRGB::from(hsv));
+                    assert_eq!(HSV::from(HSV16::from(hsv)), hsv);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hsv16_interpolates() {
+        for h in 0..=255u16 {
+            let lower = ColorRGB::from(HSV::new(h as u8, 255, 255));
+            let upper = ColorRGB::from(HSV::new((h as u8).wrapping_add(1), 255, 255));
+            for fract in (0..256).step_by(16) {
+                let c = ColorRGB::from(HSV16::new(h << 8 | fract, 255, 255));
+                for i in 0..3 {
+                    assert!(c[i] >= lower[i].min(upper[i]) && c[i] <= lower[i].max(upper[i]));
+                }
+            }
+        }
+        let c = ColorRGB::from(HSV16::new(0xFF80, 255, 255));
+        assert_eq!(c, ColorRGB::new(254, 0, 1));
+    }
+
+    #[test]
+    fn rainbow_fill16() {
+        let mut colors = [ColorRGB::Black; 8];
+        colors.rainbow_fill16(0x1000, 0x0080);
+        for (i, c) in colors.iter().enumerate() {
+            assert_eq!(
+                *c,
+                ColorRGB::from(HSV16::new(0x1000 + 0x80 * i as u16, 255, 255))
+            );
+        }
+        assert_ne!(colors[0], colors[1]);
+
+        let mut hues = [HSV16::BLANK; 3];
+        hues.rainbow_fill16_with_sat_val(0xFFF0, 0x0010, 100, 50);
+        assert_eq!(
+            hues,
+            [
+                HSV16::new(0xFFF0, 100, 50),
+                HSV16::new(0x0000, 100, 50),
+                HSV16::new(0x0010, 100, 50)
+            ]
+        );
+    }
+
+    #[test]
+    fn hsv2rgb_spectrum_full() {
+        assert_eq!(HSV::new(0, 255, 255).to_rgb_spectrum_full(), ColorRGB::Red);
+        assert_eq!(
+            HSV::new(85, 255, 255).to_rgb_spectrum_full(),
+            ColorRGB::new(2, 255, 0)
+        );
+        assert_eq!(
+            HSV::new(171, 255, 255).to_rgb_spectrum_full(),
+            ColorRGB::new(2, 0, 255)
+        );
+        assert_eq!(HSV::new(99, 0, 255).to_rgb_spectrum_full(), ColorRGB::White);
+        assert_eq!(HSV::new(99, 255, 0).to_rgb_spectrum_full(), ColorRGB::Black);
+        for h in 0..=255 {
+            for s in (0..=255).step_by(15) {
+                for v in (0..=255).step_by(15) {
+                    let rgb = HSV::new(h as u8, s as u8, v as u8).to_rgb_spectrum_full();
+                    assert_eq!(rgb.r.max(rgb.g).max(rgb.b), v as u8);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hsv2rgb_spectrum_round_trips() {
+        for code in (0..0xFF_FFFFu32).step_by(0x1357) {
+            let color = ColorRGB::from_color_code(code);
+            let back = HSV::from_rgb_spectrum(color).to_rgb_spectrum_full();
+            // With only 256 hues, saturated colors can be off by a couple of steps.
+            for i in 0..3 {
+                assert!((i16::from(back[i]) - i16::from(color[i])).abs() <= 4);
+            }
+        }
+    }
+
+    #[test]
+    fn hsv2rgb_spectrum_fast() {
+        for h in 0..=255 {
+            let hsv = HSV::new(h as u8, 255, 255);
+            assert_eq!(hsv.to_rgb_spectrum_fast(), hsv.to_rgb_spectrum_full());
+            for s in (0..=255).step_by(15) {
+                for v in (0..=255).step_by(15) {
+                    let hsv = HSV::new(h as u8, s as u8, v as u8);
+                    let (fast, full) = (hsv.to_rgb_spectrum_fast(), hsv.to_rgb_spectrum_full());
+                    for i in 0..3 {
+                        assert!((i16::from(fast[i]) - i16::from(full[i])).abs() <= 2);
+                    }
+                }
+            }
+        }
+    }
 }
@@ -8,13 +8,28 @@
 //!    brightening single and double byte values. Basic trigonometric functions (sine, cosine)
 //!    are implemented as well.
 //!
-//! - **HSV and RGB support** — Full control over each color is provided by the `HSV` and
-//!   `ColorRGB` structures. Different means of converting from `HSV` to `ColorRGB` are also
-//!   implemented.
+//! - **HSV, HSL and RGB support** — Full control over each color is provided by the `HSV`,
+//!   `HSL`, and `ColorRGB` structures. Different means of converting from `HSV` to `ColorRGB`
+//!   are also implemented. `ColorRGBA` adds an alpha channel for compositing transparent colors.
 //!
 //! - **Axial (Two Point) Color Gradients** — Create smooth transitions between any two colors
 //!   for any number of steps.
 //!
+//! - **Blend Modes** — Stack layers of color with `BlendMode`s such as `Multiply`, `Screen`,
+//!   and `Overlay`, using only integer math. The `Compositor` flattens entire stacks of
+//!   layers, each with their own opacity and alpha mask.
+//!
+//! - **2D Matrices** — View a strip as a `Matrix` of pixels, with support for serpentine,
+//!   column major, rotated, and arbitrarily wired panels.
+//!
+//! - **Pixel Maps** — Drive gradients and rainbows from the physical position of each pixel
+//!   with a `PixelMap`, for rings, sculptures, and other non-grid installations. Discs of
+//!   concentric rings can be addressed by angle and radius with a `RingLayout`.
+//!
+//! - **Strip Segments** — Split a strip into `Segment`s that can run in reverse, start from an
+//!   offset, or mirror one half onto the other (with an explicit `mirror()` after filling),
+//!   while still working with every fill.
+//!
 //! - **Power Consumption Estimating** — Estimating power requirements can be done with
 //!   structs implementing the `PowerEstimator` trait.
 //!
@@ -94,15 +109,55 @@
 //!
 //! To use some unstable nightly features and optimizations, use the `nightly` feature flag.
 //!
+//! This includes a `core::simd` implementation of the `ColorSliceMut` methods for targets with
+//! 128 bit vector registers, such as NEON. `x86` and `x86_64` keep their SSE2 and AVX2 versions.
+//!
+//! # embedded-graphics
+//!
+//! The `embedded-graphics` feature implements `PixelColor` and `RgbColor` for `ColorRGB`, and
+//! `DrawTarget` for `matrix::Matrix`, allowing text, shapes, and images from the
+//! [embedded-graphics](https://crates.io/crates/embedded-graphics) crate to be drawn directly
+//! onto LED matrices.
+//!
+//! # smart-leds
+//!
+//! The `smart-leds` feature adds the `smart_leds` module, converting between `ColorRGB` and the
+//! `RGB8` and `RGBW` colors taken by [smart-leds](https://crates.io/crates/smart-leds) drivers.
+//!
+//! # serde
+//!
+//! The `serde` feature implements `Serialize` and `Deserialize` for `ColorRGB`, `HSV`, and
+//! `GradientDirection`. In human readable formats such as JSON, a `ColorRGB` is written as a
+//! `"#RRGGBB"` string, while binary formats store it as three bytes.
+//!
+//! # perceptual
+//!
+//! The `perceptual` feature adds the `perceptual` module, converting `ColorRGB` to and from
+//! linear-light RGB, CIE XYZ, CIE Lab, and OKLab. These use `f32`, with
+//! [libm](https://crates.io/crates/libm) providing the math functions under `no-std`. Integer
+//! only approximations of Lab and OKLab are included for micro-controllers without an FPU.
+//! `GradientFillOklab` fills strips with gradients interpolated in OKLab or linear light.
+//!
 //! # Acknowledgements
 //!
 //! This library takes heavy inspiration and code-reuse from
 //! [FastLED](https://github.com/FastLED/FastLED), an Arduino library for talking to addressable
 //! LEDs.
 
-// TODO: SERDE
 #![cfg_attr(feature = "no-std", no_std)]
 #![cfg_attr(feature = "nightly", feature(link_llvm_intrinsics))]
+#![cfg_attr(
+    all(
+        feature = "nightly",
+        any(
+            test,
+            target_feature = "neon",
+            target_feature = "simd128",
+            target_feature = "altivec"
+        )
+    ),
+    feature(portable_simd)
+)]
 //#![feature(link_llvm_intrinsics)]
 
 macro_rules! mk_rgb {
@@ -119,18 +174,40 @@ macro_rules! mk_rgb {
 //    ($h:expr) => {crate::hsv::HSV::new($h, 255, 255)};
 //}
 
+mod blend;
 pub mod color_codes;
 mod color_util;
+mod compositor;
+#[cfg(feature = "embedded-graphics")]
+mod embedded_graphics;
+mod hsl;
 mod hsv;
 pub mod math;
+pub mod matrix;
+#[cfg(feature = "perceptual")]
+pub mod perceptual;
+pub mod pixel_map;
+pub mod polar;
+mod parse;
 mod power_mgmt;
 mod rgb;
+mod rgba;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod segment;
+#[cfg(feature = "smart-leds")]
+pub mod smart_leds;
 
+pub use crate::blend::BlendMode;
 pub use crate::color_util::GradientDirection;
-pub use crate::hsv::HSV;
+pub use crate::compositor::{Compositor, Layer};
+pub use crate::hsl::HSL;
+pub use crate::hsv::{HSV, HSV16};
+pub use crate::parse::{ParseColorError, ParseColorErrorKind};
 
 pub use crate::power_mgmt::{DefaultPowerEstimator, PowerEstimator};
 pub use crate::rgb::ColorRGB;
+pub use crate::rgba::ColorRGBA;
 pub use crate::color_util::gradient::{hsv_gradient,rgb_gradient};
 
 
@@ -150,5 +227,11 @@ pub mod prelude {
     pub use crate::color_util::GradientFillRGBToInclusive;
 
     pub use crate::color_util::RainbowFill;
+    pub use crate::color_util::RainbowFill16;
     pub use crate::color_util::RainbowFillSingleCycle;
+
+    #[cfg(feature = "perceptual")]
+    pub use crate::perceptual::GradientFillOklab;
+    #[cfg(feature = "perceptual")]
+    pub use crate::perceptual::GradientFillOklabToInclusive;
 }
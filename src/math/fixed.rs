@@ -0,0 +1,73 @@
+//! Signed fixed point numbers.
+
+/// A signed fixed point number with 8 integer bits and 8 fractional bits.
+///
+/// The value represented is `bits / 256`, covering `-128.0` up to `127.99609375` in steps of
+/// `1/256`. This is most useful for moving things by fractions of a pixel.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::math::Q8_8;
+///
+/// let one_and_a_half = Q8_8::new(1, 128);
+/// assert_eq!(one_and_a_half.to_bits(), 384);
+///
+/// let minus_a_quarter = Q8_8::from_bits(-64);
+/// assert_eq!(minus_a_quarter.whole(), -1);
+/// assert_eq!(minus_a_quarter.fract(), 192);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash)]
+pub struct Q8_8(i16);
+
+impl Q8_8 {
+    /// Zero.
+    pub const ZERO: Q8_8 = Q8_8(0);
+
+    /// Creates a number from a `whole` integer part plus `fract / 256`.
+    ///
+    /// Wraps around if the result is out of range.
+    #[inline(always)]
+    pub const fn new(whole: i8, fract: u8) -> Self {
+        Q8_8(((whole as i16) << 8).wrapping_add(fract as i16))
+    }
+
+    /// Creates a number from an integer.
+    #[inline(always)]
+    pub const fn from_int(whole: i8) -> Self {
+        Q8_8((whole as i16) << 8)
+    }
+
+    /// Creates a number from its raw bits, where `256` is `1.0`.
+    #[inline(always)]
+    pub const fn from_bits(bits: i16) -> Self {
+        Q8_8(bits)
+    }
+
+    /// Returns the raw bits, where `256` is `1.0`.
+    #[inline(always)]
+    pub const fn to_bits(self) -> i16 {
+        self.0
+    }
+
+    /// Returns the integer part, rounded towards negative infinity.
+    #[inline(always)]
+    pub const fn whole(self) -> i8 {
+        (self.0 >> 8) as i8
+    }
+
+    /// Returns the fractional part as a numerator over `256`.
+    ///
+    /// This is always positive, so `whole() + fract() / 256` is the value represented.
+    #[inline(always)]
+    pub const fn fract(self) -> u8 {
+        self.0 as u8
+    }
+}
+
+impl From<i8> for Q8_8 {
+    #[inline(always)]
+    fn from(whole: i8) -> Self {
+        Q8_8::from_int(whole)
+    }
+}
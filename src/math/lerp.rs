@@ -1,68 +1,253 @@
 //! Linear Interpolation functions and structures.
 
 #[cfg(feature = "no-std")]
-use core::mem::transmute;
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
 #[cfg(not(feature = "no-std"))]
-use std::mem::transmute;
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
 
-/// Three Dimension Linear Interpolation
-pub struct ThreePointLerp {
-    pub delta: [i16; 3],
-    pub accum: [u16; 3],
+/// Half of a single step in 16.16 fixed point, used to round to the nearest integer.
+const ROUNDING_BIAS: u32 = 0x8000;
+
+/// `N` Dimensional Linear Interpolation using 16.16 fixed point accumulators.
+///
+/// Each channel is a `u16`, and is interpolated independently from a starting value
+/// towards an end value over a set number of divisions. Channels are allowed to wrap
+/// around, meaning a distance (see [`Lerp::from_distance`]) can be used to interpolate
+/// hues the "long" way around the color wheel.
+///
+/// Every value is rounded to the nearest integer, so the interpolated values land
+/// exactly on the end value for any number of divisions up to `65535`.
+///
+/// In addition to iterating forwards, `Lerp` can be iterated in reverse and supports
+/// random access through `Iterator::nth()` without stepping over each skipped value.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::math::Lerp;
+///
+/// let lerp = Lerp::new_inclusive([0, 255, 100], [255, 0, 100], 4);
+/// let values: Vec<[u16; 3]> = lerp.collect();
+/// assert_eq!(values, [[0, 255, 100], [85, 170, 100], [170, 85, 100], [255, 0, 100]]);
+/// ```
+///
+/// Random access and reverse iteration:
+///
+/// ```
+/// use cichlid::math::Lerp;
+///
+/// let mut lerp = Lerp::new([0; 4], [1000, 2000, 3000, 4000], 1000);
+/// assert_eq!(lerp.len(), 1000);
+/// assert_eq!(lerp.nth(500), Some([500, 1000, 1500, 2000]));
+/// assert_eq!(lerp.next_back(), Some([999, 1998, 2997, 3996]));
+/// ```
+///
+/// [`Lerp::from_distance`]: #method.from_distance
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lerp<const N: usize> {
+    origin: [u32; N],
+    delta: [u32; N],
+    accum: [u32; N],
+    front: usize,
+    back: usize,
 }
 
-impl ThreePointLerp {
-    #[inline(always)]
-    pub const fn new() -> Self {
-        ThreePointLerp {
-            delta: [0; 3],
-            accum: [0; 3],
-        }
+impl<const N: usize> Lerp<N> {
+    /// Creates an interpolation from `start` to (exclusive) `end`, yielding `steps` values.
+    ///
+    /// The first value yielded is `start`, and the value that would come after the
+    /// last is `end`.
+    #[inline]
+    pub fn new(start: [u16; N], end: [u16; N], steps: usize) -> Self {
+        Self::from_distance(start, distances(start, end), steps)
     }
 
-    #[inline(always)]
-    pub fn set_lerp_from_diff(self, num: usize, start: u8, end: u8) -> Self {
-        let distance: i16 = (i16::from(end).wrapping_sub(i16::from(start))).wrapping_shl(7);
-        self.set_lerp_from_distance(num, start, distance)
+    /// Creates an interpolation from `start` to (inclusive) `end`, yielding `steps` values.
+    ///
+    /// The first value yielded is `start` and the last value yielded is `end`. If `steps`
+    /// is one, only `start` is yielded.
+    #[inline]
+    pub fn new_inclusive(start: [u16; N], end: [u16; N], steps: usize) -> Self {
+        Self::with_divisions(start, distances(start, end), steps.saturating_sub(1), steps)
     }
 
-    #[inline(always)]
-    pub fn set_lerp_from_distance(mut self, num: usize, start: u8, distance: i16) -> Self {
-        assert!(num <= 2);
-        self.delta[num] = distance;
-        self.accum[num] = u16::from(start) << 8;
-        self
+    /// Creates an interpolation starting at `start`, and moving each channel by the signed
+    /// `distance` over `steps` values, exclusive of the final value.
+    ///
+    /// Channels wrap around on overflow and underflow. This is useful for values such as
+    /// hues, which can move in either direction around the color wheel.
+    #[inline]
+    pub fn from_distance(start: [u16; N], distance: [i32; N], steps: usize) -> Self {
+        Self::with_divisions(start, distance, steps, steps)
+    }
+
+    fn with_divisions(start: [u16; N], distance: [i32; N], divisions: usize, len: usize) -> Self {
+        let mut origin = [0u32; N];
+        let mut delta = [0u32; N];
+        origin
+            .iter_mut()
+            .zip(start.iter())
+            .for_each(|(o, s)| *o = (u32::from(*s) << 16).wrapping_add(ROUNDING_BIAS));
+        if divisions != 0 {
+            let div = divisions as i64;
+            delta.iter_mut().zip(distance.iter()).for_each(|(d, dist)| {
+                *d = ((i64::from(*dist) << 16) + div / 2).div_euclid(div) as u32
+            });
+        }
+        Lerp {
+            origin,
+            delta,
+            accum: origin,
+            front: 0,
+            back: len,
+        }
     }
 
+    /// Returns the fixed point accumulators for the value at index `idx`.
     #[inline(always)]
-    pub fn modify_delta<F>(mut self, mut f: F) -> Self
-    where
-        for<'w> F: FnMut(i16) -> i16,
-    {
-        self.delta.iter_mut().for_each(|x| *x = f(*x));
-        self
+    fn accum_at(&self, idx: usize) -> [u32; N] {
+        let mut accum = self.origin;
+        accum
+            .iter_mut()
+            .zip(self.delta.iter())
+            .for_each(|(a, d)| *a = a.wrapping_add(d.wrapping_mul(idx as u32)));
+        accum
     }
 
     #[inline(always)]
-    fn lerp(&self) -> (u8, u8, u8) {
-        (
-            (self.accum[0] >> 8) as u8,
-            (self.accum[1] >> 8) as u8,
-            (self.accum[2] >> 8) as u8,
-        )
+    fn lerp(accum: &[u32; N]) -> [u16; N] {
+        let mut out = [0u16; N];
+        out.iter_mut()
+            .zip(accum.iter())
+            .for_each(|(o, a)| *o = (a >> 16) as u16);
+        out
     }
 }
 
-impl Iterator for ThreePointLerp {
-    type Item = (u8, u8, u8);
+/// Signed difference between each channel of `end` and `start`.
+#[inline(always)]
+fn distances<const N: usize>(start: [u16; N], end: [u16; N]) -> [i32; N] {
+    let mut distance = [0i32; N];
+    distance
+        .iter_mut()
+        .zip(start.iter().zip(end.iter()))
+        .for_each(|(d, (s, e))| *d = i32::from(*e) - i32::from(*s));
+    distance
+}
+
+impl<const N: usize> Iterator for Lerp<N> {
+    type Item = [u16; N];
 
     #[inline]
-    fn next(&mut self) -> Option<(u8, u8, u8)> {
-        let tuple: (u8, u8, u8) = self.lerp();
+    fn next(&mut self) -> Option<[u16; N]> {
+        if self.front >= self.back {
+            return None;
+        }
+        let out = Self::lerp(&self.accum);
         self.accum
             .iter_mut()
             .zip(self.delta.iter())
-            .for_each(|(a, d)| *a = a.wrapping_add(unsafe { transmute::<i16, u16>(*d) }));
-        Some(tuple)
+            .for_each(|(a, d)| *a = a.wrapping_add(*d));
+        self.front += 1;
+        Some(out)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<[u16; N]> {
+        if n >= self.len() {
+            self.front = self.back;
+            return None;
+        }
+        self.front += n;
+        self.accum = self.accum_at(self.front);
+        self.next()
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for Lerp<N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<[u16; N]> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(Self::lerp(&self.accum_at(self.back)))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for Lerp<N> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl<const N: usize> FusedIterator for Lerp<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::Lerp;
+
+    #[test]
+    fn lerp_exclusive_ends() {
+        let mut lerp = Lerp::new([0, 200], [100, 0], 5);
+        assert_eq!(lerp.next(), Some([0, 200]));
+        assert_eq!(lerp.next_back(), Some([80, 40]));
+    }
+
+    #[test]
+    fn lerp_inclusive_long() {
+        for &len in [2usize, 3, 7, 255, 1000, 1021, 4096, 65535].iter() {
+            let start = [255, 0, 12345, 7];
+            let end = [0, 65535, 54321, 7];
+            let lerp = Lerp::new_inclusive(start, end, len);
+            assert_eq!(lerp.len(), len);
+            assert_eq!(lerp.clone().next(), Some(start));
+            // Iterate forwards to check the accumulators land on the end value.
+            let last = lerp.fold(None, |_, v| Some(v));
+            assert_eq!(last, Some(end), "len: {}", len);
+        }
+    }
+
+    #[cfg(not(feature = "no-std"))]
+    #[test]
+    fn lerp_wrapping_distance() {
+        let lerp = Lerp::from_distance([250], [-20], 4);
+        let hues: Vec<u8> = lerp.map(|[h]| h as u8).collect();
+        assert_eq!(hues, [250, 245, 240, 235]);
+
+        let lerp = Lerp::from_distance([250], [20], 4);
+        let hues: Vec<u8> = lerp.map(|[h]| h as u8).collect();
+        assert_eq!(hues, [250, 255, 4, 9]);
+    }
+
+    #[cfg(not(feature = "no-std"))]
+    #[test]
+    fn lerp_nth_and_rev_match_forward() {
+        let lerp = Lerp::new_inclusive([3, 60000, 0], [255, 10, 1], 1337);
+        let forward: Vec<[u16; 3]> = lerp.clone().collect();
+        let mut reverse: Vec<[u16; 3]> = lerp.clone().rev().collect();
+        reverse.reverse();
+        assert_eq!(forward, reverse);
+
+        for i in (0..1337).step_by(17) {
+            assert_eq!(lerp.clone().nth(i), Some(forward[i]));
+        }
+        assert_eq!(lerp.clone().nth(1337), None);
+    }
+
+    #[cfg(not(feature = "no-std"))]
+    #[test]
+    fn lerp_empty_and_single() {
+        assert_eq!(Lerp::new([1], [2], 0).next(), None);
+        assert_eq!(Lerp::new_inclusive([1], [2], 0).next(), None);
+        let single: Vec<[u16; 1]> = Lerp::new_inclusive([1], [2], 1).collect();
+        assert_eq!(single, [[1]]);
     }
 }
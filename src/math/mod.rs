@@ -5,6 +5,9 @@
 //! - In place and batch scaling (`nscale16x3` for example).
 //! - Dimming and Brightening Functions
 //! - Fast u8 and u16 trigonometric functions
+//! - Integer `atan2` and square root
+//! - Multi-channel fixed point linear interpolation (`Lerp`)
+//! - Signed 8.8 fixed point numbers (`Q8_8`)
 //! - Other useful operations, such as blending integers.
 //!
 //! This module offers a couple different ways to access the m
@@ -26,7 +29,8 @@
 // https://doc.rust-lang.org/edition-guide/rust-2018/simd-for-faster-computing.html
 
 pub(crate) mod ext;
-pub(crate) mod lerp;
+pub(crate) mod fixed;
+pub mod lerp;
 pub(crate) mod trig;
 
 pub use math_u16_impls::blend as blend_u16;
@@ -56,7 +60,10 @@ pub use math_u8_impls::nscale_x4 as nscale_u8x4;
 pub use math_u8_impls::scale as scale_u8;
 pub use math_u8_impls::scale_video as scale_u8_video;
 
+pub use fixed::Q8_8;
+pub use lerp::Lerp;
 pub use trig::{sin_u8,cos_u8,sin_u16,cos_u16};
+pub use trig::{atan2_u16, sqrt_u64};
 
 /// Basic trigonometric functions for integers.
 pub trait Trig<Signed> {
@@ -42,6 +42,53 @@ pub fn cos_u8(theta: u8) -> u8 {
     sin_u8(theta.wrapping_add(64))
 }
 
+/// Returns the angle of the point `(x, y)` from the positive x axis, where `65536` is a
+/// complete turn.
+///
+/// Angles increase from the positive x axis towards the positive y axis, and are accurate
+/// to within `48 / 65536` of a turn. The angle of `(0, 0)` is zero.
+pub fn atan2_u16(y: i32, x: i32) -> u16 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+    let (ax, ay) = (u64::from(x.unsigned_abs()), u64::from(y.unsigned_abs()));
+    let (min, max) = if ay <= ax { (ay, ax) } else { (ax, ay) };
+    // Ratio of the shorter side to the longer side, in 16.16 fixed point.
+    let r: u64 = (min << 16) / max;
+    // atan(r) ~= r * PI/4 + 0.273 * r * (1 - r), scaled to an eighth of a turn at r = 1.
+    let mut angle: u32 = ((8192 * r + 2847 * ((r * (65536 - r)) >> 16)) >> 16) as u32;
+    if ay > ax {
+        angle = 16384 - angle;
+    }
+    if x < 0 {
+        angle = 32768 - angle;
+    }
+    if y < 0 {
+        angle = 65536 - angle;
+    }
+    angle as u16
+}
+
+/// Returns the integer square root of `x`, rounded down.
+pub fn sqrt_u64(x: u64) -> u32 {
+    let mut rem: u64 = x;
+    let mut root: u64 = 0;
+    let mut bit: u64 = 1 << 62;
+    while bit > rem {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root as u32
+}
+
 #[cfg(feature = "low-mem")]
 mod trig_inner {
     #[cfg(feature = "no-std")]
@@ -131,7 +178,41 @@ impl super::Trig<i16> for u16 {
 
 #[cfg(test)]
 mod test {
-    use crate::math::trig::{cos_u8, sin_u8};
+    use crate::math::trig::{atan2_u16, cos_u8, sin_u8, sqrt_u64};
+
+    #[test]
+    fn atan2_axes() {
+        assert_eq!(atan2_u16(0, 0), 0);
+        assert_eq!(atan2_u16(0, 5), 0);
+        assert_eq!(atan2_u16(5, 0), 16384);
+        assert_eq!(atan2_u16(0, -5), 32768);
+        assert_eq!(atan2_u16(-5, 0), 49152);
+        assert_eq!(atan2_u16(7, 7), 8192);
+    }
+
+    #[cfg(not(feature = "no-std"))]
+    #[test]
+    fn atan2_accuracy() {
+        for y in (-1000..=1000).step_by(37) {
+            for x in (-1000..=1000).step_by(41) {
+                let expected = (f64::from(y).atan2(f64::from(x)) / (2.0 * core::f64::consts::PI)
+                    * 65536.0)
+                    .rem_euclid(65536.0);
+                let actual = f64::from(atan2_u16(y, x));
+                let diff = (actual - expected).abs();
+                assert!(diff.min(65536.0 - diff) <= 48.0, "({}, {}): {}", x, y, actual);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt() {
+        for x in (0..1_000_000u64).step_by(7) {
+            let root = u64::from(sqrt_u64(x));
+            assert!(root * root <= x && (root + 1) * (root + 1) > x, "{}", x);
+        }
+        assert_eq!(sqrt_u64(u64::MAX), u32::MAX);
+    }
 
     #[test]
     fn all_sin() {
@@ -0,0 +1,303 @@
+//! Anti-aliased drawing onto matrices.
+//!
+//! Positions are fixed point with 8 fractional bits, so `256` is one pixel. The pixel at
+//! `(x, y)` covers the square from `(x * 256, y * 256)` up to (exclusive)
+//! `((x + 1) * 256, (y + 1) * 256)`, and shapes are blended into each pixel by how much of it
+//! they cover.
+
+use super::Matrix;
+use crate::color_util::color_impls::{coverage_u8, span_coverage};
+use crate::math::sqrt_u64;
+use crate::{BlendMode, ColorRGB};
+
+impl<'a> Matrix<'a> {
+    /// Combines `color` on top of the pixel at `(x, y)` by `coverage` out of `256`. Out of
+    /// bounds coordinates are ignored.
+    #[inline]
+    fn plot(&mut self, x: i32, y: i32, color: ColorRGB, mode: BlendMode, coverage: u32) {
+        if x >= 0 && y >= 0 && coverage != 0 {
+            if let Some(pixel) = self.get_mut(x as usize, y as usize) {
+                *pixel = mode.blend(*pixel, color, coverage_u8(coverage));
+            }
+        }
+    }
+
+    /// Draws a single pixel sized square with its top left corner at `(x, y)`, spread over
+    /// the (up to) four pixels it overlaps.
+    ///
+    /// This is a Xiaolin Wu style "splat", allowing a point to move smoothly between pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{BlendMode, ColorRGB};
+    /// use cichlid::matrix::{Layout, Matrix};
+    ///
+    /// let mut leds = [ColorRGB::Black; 4 * 4];
+    /// let mut matrix = Matrix::new(&mut leds, 4, 4, Layout::Serpentine);
+    ///
+    /// // Halfway between (1, 2) and (2, 2).
+    /// matrix.wu_pixel(384, 512, ColorRGB::White, BlendMode::Normal);
+    /// assert_eq!(matrix.get(1, 2), Some(ColorRGB::new(128, 128, 128)));
+    /// assert_eq!(matrix.get(2, 2), Some(ColorRGB::new(128, 128, 128)));
+    /// assert_eq!(matrix.get(1, 3), Some(ColorRGB::Black));
+    /// ```
+    pub fn wu_pixel(&mut self, x: i32, y: i32, color: ColorRGB, mode: BlendMode) {
+        let (px, py) = (x >> 8, y >> 8);
+        let (fx, fy) = ((x & 0xFF) as u32, (y & 0xFF) as u32);
+        self.plot(px, py, color, mode, ((256 - fx) * (256 - fy)) >> 8);
+        self.plot(px + 1, py, color, mode, (fx * (256 - fy)) >> 8);
+        self.plot(px, py + 1, color, mode, ((256 - fx) * fy) >> 8);
+        self.plot(px + 1, py + 1, color, mode, (fx * fy) >> 8);
+    }
+
+    /// Draws an anti-aliased line one pixel thick, as if `wu_pixel()` was drawn at every
+    /// point from `(x0, y0)` to `(x1, y1)`.
+    ///
+    /// A line with integer positions covers every pixel it passes through, including the
+    /// pixels at both ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{BlendMode, ColorRGB};
+    /// use cichlid::matrix::{Layout, Matrix};
+    ///
+    /// let mut leds = [ColorRGB::Black; 4 * 4];
+    /// let mut matrix = Matrix::new(&mut leds, 4, 4, Layout::RowMajor);
+    ///
+    /// // The diagonal from (0, 0) to (3, 3).
+    /// matrix.line(0, 0, 768, 768, ColorRGB::Red, BlendMode::Normal);
+    /// assert!((0..4).all(|i| matrix.get(i, i) == Some(ColorRGB::Red)));
+    /// assert_eq!(matrix.get(1, 0), Some(ColorRGB::Black));
+    /// ```
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: ColorRGB, mode: BlendMode) {
+        // Steps along the major axis `a` one pixel at a time, splitting each step over the
+        // two pixels of the minor axis `m` it lands between.
+        let steep: bool =
+            (i64::from(y1) - i64::from(y0)).abs() > (i64::from(x1) - i64::from(x0)).abs();
+        let ((a0, m0), (a1, m1)) = match steep {
+            true => ((y0, x0), (y1, x1)),
+            false => ((x0, y0), (x1, y1)),
+        };
+        let ((a0, m0), (a1, m1)) = match a0 <= a1 {
+            true => ((a0, m0), (a1, m1)),
+            false => ((a1, m1), (a0, m0)),
+        };
+        let end: i32 = a1.saturating_add(256);
+        let last: i32 = match steep {
+            true => self.height() as i32 - 1,
+            false => self.width() as i32 - 1,
+        };
+        let da: i64 = i64::from(a1) - i64::from(a0);
+        let dm: i64 = i64::from(m1) - i64::from(m0);
+        for c in (a0 >> 8).max(0)..=((end - 1) >> 8).min(last) {
+            let along: u32 = span_coverage(a0, end, c);
+            if along == 0 {
+                continue;
+            }
+            let u: i64 = (i64::from(c) << 8).max(i64::from(a0)).min(i64::from(a1));
+            let m: i64 = match da {
+                0 => i64::from(m0),
+                _ => {
+                    i64::from(m0)
+                        + (i128::from(u - i64::from(a0)) * i128::from(dm) / i128::from(da)) as i64
+                }
+            };
+            let (pm, fm) = ((m >> 8) as i32, (m & 0xFF) as u32);
+            let (near, far) = ((along * (256 - fm)) >> 8, (along * fm) >> 8);
+            match steep {
+                true => {
+                    self.plot(pm, c, color, mode, near);
+                    self.plot(pm + 1, c, color, mode, far);
+                }
+                false => {
+                    self.plot(c, pm, color, mode, near);
+                    self.plot(c, pm + 1, color, mode, far);
+                }
+            }
+        }
+    }
+
+    /// Draws the outline of an anti-aliased circle one pixel thick, centered on
+    /// `(cx, cy)`.
+    ///
+    /// Each pixel is blended in by how close its center is to the circle, so a `radius` of
+    /// zero draws a single (possibly blurred) point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{BlendMode, ColorRGB};
+    /// use cichlid::matrix::{Layout, Matrix};
+    ///
+    /// let mut leds = [ColorRGB::Black; 8 * 8];
+    /// let mut matrix = Matrix::new(&mut leds, 8, 8, Layout::Serpentine);
+    ///
+    /// // Centered on the middle of pixel (3, 3), three pixels wide.
+    /// matrix.circle(896, 896, 768, ColorRGB::Blue, BlendMode::Add);
+    /// assert_eq!(matrix.get(6, 3), Some(ColorRGB::Blue));
+    /// assert_eq!(matrix.get(3, 0), Some(ColorRGB::Blue));
+    /// assert_eq!(matrix.get(3, 3), Some(ColorRGB::Black));
+    /// ```
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: i32, color: ColorRGB, mode: BlendMode) {
+        let radius: i64 = i64::from(radius.max(0));
+        let (cx, cy) = (i64::from(cx), i64::from(cy));
+        let reach: i64 = radius + 256;
+        let x_range = ((cx - reach) >> 8).max(0)..=((cx + reach) >> 8).min(self.width() as i64 - 1);
+        let y_range =
+            ((cy - reach) >> 8).max(0)..=((cy + reach) >> 8).min(self.height() as i64 - 1);
+        for y in y_range {
+            for x in x_range.clone() {
+                let dx: u64 = ((x << 8) + 128 - cx).unsigned_abs();
+                let dy: u64 = ((y << 8) + 128 - cy).unsigned_abs();
+                // Overflowing squares are far beyond the largest possible radius.
+                let dist_sq: Option<u64> = dx
+                    .checked_mul(dx)
+                    .and_then(|dx2| dy.checked_mul(dy).and_then(|dy2| dx2.checked_add(dy2)));
+                let dist: i64 = match dist_sq {
+                    Some(dist_sq) => i64::from(sqrt_u64(dist_sq)),
+                    None => continue,
+                };
+                let coverage: i64 = (256 - (dist - radius).abs()).max(0);
+                self.plot(x as i32, y as i32, color, mode, coverage as u32);
+            }
+        }
+    }
+
+    /// Draws an anti-aliased rectangle filled with `color`, with corners at `(x0, y0)` and
+    /// (exclusive) `(x1, y1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{BlendMode, ColorRGB};
+    /// use cichlid::matrix::{Layout, Matrix};
+    ///
+    /// let mut leds = [ColorRGB::White; 4 * 4];
+    /// let mut matrix = Matrix::new(&mut leds, 4, 4, Layout::ColumnMajor);
+    ///
+    /// // Covers (1, 1) and half of (2, 1).
+    /// matrix.filled_rect(256, 256, 640, 512, ColorRGB::Red, BlendMode::Multiply);
+    /// assert_eq!(matrix.get(1, 1), Some(ColorRGB::Red));
+    /// assert_eq!(matrix.get(2, 1), Some(ColorRGB::new(255, 127, 127)));
+    /// assert_eq!(matrix.get(1, 2), Some(ColorRGB::White));
+    /// ```
+    pub fn filled_rect(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        color: ColorRGB,
+        mode: BlendMode,
+    ) {
+        let (x0, x1) = (x0.min(x1), x0.max(x1));
+        let (y0, y1) = (y0.min(y1), y0.max(y1));
+        let x_range = (x0 >> 8).max(0)..(x1.saturating_add(255) >> 8).min(self.width() as i32);
+        let y_range = (y0 >> 8).max(0)..(y1.saturating_add(255) >> 8).min(self.height() as i32);
+        for y in y_range {
+            let cover_y: u32 = span_coverage(y0, y1, y);
+            for x in x_range.clone() {
+                let coverage: u32 = (span_coverage(x0, x1, x) * cover_y) >> 8;
+                self.plot(x, y, color, mode, coverage);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::matrix::Layout;
+
+    fn total(matrix: &Matrix) -> u32 {
+        matrix.pixels().iter().map(|c| u32::from(c.r)).sum()
+    }
+
+    #[test]
+    fn wu_pixel_weights() {
+        let mut leds = [ColorRGB::Black; 5 * 5];
+        let mut matrix = Matrix::new(&mut leds, 5, 5, Layout::Serpentine);
+        matrix.wu_pixel(512, 512, ColorRGB::Red, BlendMode::Normal);
+        assert_eq!(matrix.get(2, 2), Some(ColorRGB::Red));
+        assert_eq!(total(&matrix), 255);
+
+        for &(x, y) in [(64, 64), (600, 200), (1000, 1000), (-128, -128)].iter() {
+            matrix
+                .pixels_mut()
+                .iter_mut()
+                .for_each(|c| *c = ColorRGB::Black);
+            matrix.wu_pixel(x, y, ColorRGB::Red, BlendMode::Add);
+            assert!(total(&matrix) <= 256, "{:?}", (x, y));
+        }
+        assert_eq!(matrix.get(0, 0), Some(ColorRGB::new(64, 0, 0)));
+    }
+
+    #[test]
+    fn lines() {
+        let mut leds = [ColorRGB::Black; 6 * 4];
+        let mut matrix = Matrix::new(&mut leds, 6, 4, Layout::VerticalSerpentine);
+        matrix.line(0, 256, 1280, 256, ColorRGB::Red, BlendMode::Normal);
+        assert!((0..6).all(|x| matrix.get(x, 1) == Some(ColorRGB::Red)));
+        assert_eq!(total(&matrix), 6 * 255);
+
+        // Steep lines and reversed end points.
+        let mut leds = [ColorRGB::Black; 6 * 4];
+        let mut matrix = Matrix::new(&mut leds, 6, 4, Layout::Serpentine);
+        matrix.line(640, 768, 640, 0, ColorRGB::Red, BlendMode::Normal);
+        assert!((0..4).all(|y| matrix.get(2, y) == Some(ColorRGB::new(128, 0, 0))));
+        assert!((0..4).all(|y| matrix.get(3, y) == Some(ColorRGB::new(128, 0, 0))));
+
+        // Lines partially off the matrix.
+        let mut leds = [ColorRGB::Black; 6 * 4];
+        let mut matrix = Matrix::new(&mut leds, 6, 4, Layout::Serpentine);
+        matrix.line(-2560, -2560, 512, 512, ColorRGB::Red, BlendMode::Normal);
+        assert!((0..3).all(|i| matrix.get(i, i) == Some(ColorRGB::Red)));
+        assert_eq!(total(&matrix), 3 * 255);
+        matrix.line(
+            i32::MIN,
+            i32::MAX,
+            i32::MAX,
+            i32::MIN,
+            ColorRGB::Red,
+            BlendMode::Add,
+        );
+    }
+
+    #[test]
+    fn circles_and_rects() {
+        let mut leds = [ColorRGB::Black; 9 * 9];
+        let mut matrix = Matrix::new(&mut leds, 9, 9, Layout::RowMajor);
+        matrix.circle(1152, 1152, 1024, ColorRGB::Red, BlendMode::Normal);
+        for &(x, y) in [(0, 4), (4, 0), (8, 4), (4, 8)].iter() {
+            assert_eq!(matrix.get(x, y), Some(ColorRGB::Red));
+        }
+        assert_eq!(matrix.get(4, 4), Some(ColorRGB::Black));
+        assert_eq!(matrix.get(0, 0), Some(ColorRGB::Black));
+
+        let mut leds = [ColorRGB::Black; 9 * 9];
+        let mut matrix = Matrix::new(&mut leds, 9, 9, Layout::RowMajor);
+        matrix.filled_rect(2304, 2304, -100, 512, ColorRGB::Red, BlendMode::Normal);
+        assert_eq!(total(&matrix), 9 * 7 * 255);
+        matrix.filled_rect(0, 0, 0, 2304, ColorRGB::Blue, BlendMode::Normal);
+        assert!(matrix.pixels().iter().all(|c| c.b == 0));
+    }
+
+    #[test]
+    fn circle_extremes() {
+        let mut leds = [ColorRGB::Black; 2 * 2];
+        let mut matrix = Matrix::new(&mut leds, 2, 2, Layout::RowMajor);
+        for &(c, radius) in [(i32::MIN, i32::MAX), (i32::MAX, i32::MAX), (i32::MIN, 0)].iter() {
+            matrix.circle(c, c, radius, ColorRGB::Red, BlendMode::Normal);
+            matrix.circle(c, c / -2, radius, ColorRGB::Red, BlendMode::Normal);
+        }
+        matrix.circle(0, 0, i32::MIN, ColorRGB::Red, BlendMode::Normal);
+        assert_eq!(matrix.get(1, 1), Some(ColorRGB::Black));
+
+        // The edge of a huge circle passing through the center of (0, 0).
+        let radius: i32 = 2_000_000_000;
+        matrix.circle(128 - radius, 128, radius, ColorRGB::Red, BlendMode::Normal);
+        assert_eq!(matrix.get(0, 0), Some(ColorRGB::Red));
+    }
+}
@@ -0,0 +1,278 @@
+//! Blurring, fading, and shifting of entire matrices.
+
+use super::{LineMut, Matrix};
+use crate::color_util::color_impls::blur_scalar;
+use crate::ColorRGB;
+
+/// Direction to shift the contents of a `Matrix` towards.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum ShiftDirection {
+    /// Towards the top row.
+    Up,
+    /// Towards the bottom row.
+    Down,
+    /// Towards the leftmost column.
+    Left,
+    /// Towards the rightmost column.
+    Right,
+}
+
+/// What to do with pixels shifted off the edge of a `Matrix`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum ShiftEdge {
+    /// Pixels shifted off one edge reappear on the opposite edge.
+    Wrap,
+    /// Pixels shifted off the edge are discarded, and the opposite edge is filled with
+    /// the color.
+    Fill(ColorRGB),
+}
+
+impl<'a> Matrix<'a> {
+    /// Blurs each row of the matrix by `blur_amount`.
+    ///
+    /// Rows are blurred in the same way as `ColorSliceMut::blur()`, from left to right
+    /// regardless of how the matrix is wired.
+    pub fn blur_rows(&mut self, blur_amount: u8) {
+        let keep: u8 = 255 - blur_amount;
+        let seep: u8 = blur_amount >> 1;
+        for y in 0..self.height() {
+            blur_scalar(LineMut::new(self, y, true), keep, seep, ColorRGB::Black);
+        }
+    }
+
+    /// Blurs each column of the matrix by `blur_amount`.
+    ///
+    /// Columns are blurred in the same way as `ColorSliceMut::blur()`, from top to bottom
+    /// regardless of how the matrix is wired.
+    pub fn blur_columns(&mut self, blur_amount: u8) {
+        let keep: u8 = 255 - blur_amount;
+        let seep: u8 = blur_amount >> 1;
+        for x in 0..self.width() {
+            blur_scalar(LineMut::new(self, x, false), keep, seep, ColorRGB::Black);
+        }
+    }
+
+    /// Blurs the matrix in both dimensions by `blur_amount`.
+    ///
+    /// This is the same as calling `blur_rows()` and then `blur_columns()`.
+    #[inline]
+    pub fn blur2d(&mut self, blur_amount: u8) {
+        self.blur_rows(blur_amount);
+        self.blur_columns(blur_amount);
+    }
+
+    /// Fades each row to black by the amount returned from `fade_by` for that row.
+    ///
+    /// This allows for fading with a falloff, such as fading the bottom of the matrix faster
+    /// than the top.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    /// use cichlid::matrix::{Layout, Matrix};
+    ///
+    /// let mut leds = [ColorRGB::White; 8 * 8];
+    /// let mut matrix = Matrix::new(&mut leds, 8, 8, Layout::Serpentine);
+    ///
+    /// // Fade the top row by 8, increasing by 16 for each row further down.
+    /// matrix.fade_rows(|y| 8 + 16 * y as u8);
+    /// assert_eq!(matrix.get(0, 0), Some(ColorRGB::new(247, 247, 247)));
+    /// assert_eq!(matrix.get(0, 7), Some(ColorRGB::new(135, 135, 135)));
+    /// ```
+    pub fn fade_rows<F>(&mut self, mut fade_by: F)
+    where
+        F: FnMut(usize) -> u8,
+    {
+        for y in 0..self.height() {
+            let fade: u8 = fade_by(y);
+            if fade != 0 {
+                LineMut::new(self, y, true).for_each(|p| p.fade_to_black_by(fade));
+            }
+        }
+    }
+
+    /// Shifts the contents of the matrix by `amount` pixels towards a `direction`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    /// use cichlid::matrix::{Layout, Matrix, ShiftDirection, ShiftEdge};
+    ///
+    /// let mut leds = [ColorRGB::Black; 4 * 4];
+    /// let mut matrix = Matrix::new(&mut leds, 4, 4, Layout::Serpentine);
+    /// matrix.set(0, 1, ColorRGB::Red);
+    ///
+    /// matrix.shift(ShiftDirection::Left, 1, ShiftEdge::Wrap);
+    /// assert_eq!(matrix.get(3, 1), Some(ColorRGB::Red));
+    ///
+    /// matrix.shift(ShiftDirection::Down, 2, ShiftEdge::Fill(ColorRGB::Blue));
+    /// assert_eq!(matrix.get(3, 3), Some(ColorRGB::Red));
+    /// assert_eq!(matrix.get(0, 0), Some(ColorRGB::Blue));
+    /// ```
+    pub fn shift(&mut self, direction: ShiftDirection, amount: usize, edge: ShiftEdge) {
+        let (is_row, towards_start) = match direction {
+            ShiftDirection::Up => (false, true),
+            ShiftDirection::Down => (false, false),
+            ShiftDirection::Left => (true, true),
+            ShiftDirection::Right => (true, false),
+        };
+        let (lines, len) = match is_row {
+            true => (self.height(), self.width()),
+            false => (self.width(), self.height()),
+        };
+        if len == 0 {
+            return;
+        }
+        let mapping = self.mapping;
+        for fixed in 0..lines {
+            let index = |pos: usize| match is_row {
+                true => mapping.index(pos, fixed),
+                false => mapping.index(fixed, pos),
+            };
+            match edge {
+                ShiftEdge::Wrap => {
+                    let n: usize = match towards_start {
+                        true => amount % len,
+                        false => (len - amount % len) % len,
+                    };
+                    // Rotates by reversing both parts, then the whole line.
+                    for &(start, end) in [(0, n), (n, len), (0, len)].iter() {
+                        let (mut i, mut j) = (start, end);
+                        while i + 1 < j {
+                            j -= 1;
+                            self.pixels.swap(index(i), index(j));
+                            i += 1;
+                        }
+                    }
+                }
+                ShiftEdge::Fill(color) => {
+                    let n: usize = amount.min(len);
+                    if towards_start {
+                        for pos in 0..len - n {
+                            self.pixels[index(pos)] = self.pixels[index(pos + n)];
+                        }
+                        (len - n..len).for_each(|pos| self.pixels[index(pos)] = color);
+                    } else {
+                        for pos in (n..len).rev() {
+                            self.pixels[index(pos)] = self.pixels[index(pos - n)];
+                        }
+                        (0..n).for_each(|pos| self.pixels[index(pos)] = color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::matrix::{Layout, Orientation};
+    use crate::prelude::*;
+
+    fn pattern(seed: u32) -> [ColorRGB; 30] {
+        let mut seed = seed;
+        let mut out = [ColorRGB::Black; 30];
+        out.iter_mut().for_each(|c| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            *c = ColorRGB::from_color_code(seed);
+        });
+        out
+    }
+
+    /// Copies a row major image into a matrix of any layout.
+    fn load(matrix: &mut Matrix, image: &[ColorRGB; 30]) {
+        for y in 0..matrix.height() {
+            for x in 0..matrix.width() {
+                matrix.set(x, y, image[y * matrix.width() + x]);
+            }
+        }
+    }
+
+    /// Reads a matrix of any layout into a row major image.
+    fn read(matrix: &Matrix) -> [ColorRGB; 30] {
+        let mut out = [ColorRGB::Black; 30];
+        for y in 0..matrix.height() {
+            for x in 0..matrix.width() {
+                out[y * matrix.width() + x] = matrix.get(x, y).unwrap();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn blur_rows_matches_slices() {
+        let image = pattern(0xDEAD_BEEF);
+        let mut expected = image;
+        expected.chunks_mut(6).for_each(|row| row.blur(100));
+
+        for layout in [
+            Layout::RowMajor,
+            Layout::Serpentine,
+            Layout::VerticalSerpentine,
+        ]
+        .iter()
+        {
+            let mut leds = [ColorRGB::Black; 30];
+            let mut matrix = Matrix::new(&mut leds, 6, 5, *layout);
+            load(&mut matrix, &image);
+            matrix.blur_rows(100);
+            assert_eq!(read(&matrix)[..], expected[..], "{:?}", layout);
+        }
+    }
+
+    #[test]
+    fn blur_columns_is_transposed_rows() {
+        let image = pattern(0x0BAD_F00D);
+        let mut leds = [ColorRGB::Black; 30];
+        let mut matrix = Matrix::new(&mut leds, 6, 5, Layout::Serpentine);
+        load(&mut matrix, &image);
+        matrix.blur_columns(77);
+        let expected = read(&matrix);
+
+        let mut leds = [ColorRGB::Black; 30];
+        let mut matrix = Matrix::new(&mut leds, 6, 5, Layout::Serpentine)
+            .with_orientation(Orientation::Transpose);
+        for y in 0..5 {
+            for x in 0..6 {
+                matrix.set(y, x, image[y * 6 + x]);
+            }
+        }
+        matrix.blur_rows(77);
+        for y in 0..5 {
+            for x in 0..6 {
+                assert_eq!(matrix.get(y, x), Some(expected[y * 6 + x]));
+            }
+        }
+    }
+
+    #[test]
+    fn shifts() {
+        let image = pattern(0x1357_9BDF);
+        let mut leds = [ColorRGB::Black; 30];
+        let mut matrix = Matrix::new(&mut leds, 6, 5, Layout::VerticalSerpentine);
+        load(&mut matrix, &image);
+
+        matrix.shift(ShiftDirection::Right, 2, ShiftEdge::Wrap);
+        assert_eq!(matrix.get(2, 0), Some(image[0]));
+        assert_eq!(matrix.get(0, 0), Some(image[4]));
+        matrix.shift(ShiftDirection::Left, 8, ShiftEdge::Wrap);
+        assert_eq!(read(&matrix), image);
+
+        matrix.shift(ShiftDirection::Down, 5, ShiftEdge::Wrap);
+        assert_eq!(read(&matrix), image);
+        matrix.shift(ShiftDirection::Down, 1, ShiftEdge::Wrap);
+        matrix.shift(ShiftDirection::Up, 1, ShiftEdge::Wrap);
+        assert_eq!(read(&matrix), image);
+
+        matrix.shift(ShiftDirection::Down, 2, ShiftEdge::Fill(ColorRGB::Red));
+        assert_eq!(matrix.get(3, 4), Some(image[2 * 6 + 3]));
+        assert_eq!(matrix.get(3, 1), Some(ColorRGB::Red));
+        matrix.shift(ShiftDirection::Left, 10, ShiftEdge::Fill(ColorRGB::Blue));
+        assert!(matrix.pixels().iter().all(|c| *c == ColorRGB::Blue));
+    }
+}
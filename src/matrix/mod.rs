@@ -0,0 +1,562 @@
+//! Two dimensional views over strips of `ColorRGB`s.
+//!
+//! LED matrices are almost always wired as one long strip, snaking through the panel in some
+//! pattern. A [`Matrix`] wraps the strip's buffer with a width, height, and [`Layout`]
+//! describing the wiring, allowing colors to be accessed by `(x, y)` coordinates. An
+//! [`Orientation`] can be applied on top of the layout for panels that are mounted rotated
+//! or flipped.
+//!
+//! Rows and columns can be iterated over mutably, meaning the gradient and rainbow traits
+//! from the [`prelude`] work on them directly. Whole matrices can be blurred, faded, and
+//! shifted, with each following the layout so serpentine panels behave like any other.
+//! Anti-aliased points, lines, circles, and rectangles can be drawn at sub-pixel positions,
+//! combined onto the matrix with any `BlendMode`.
+//!
+//! # Examples
+//!
+//! ```
+//! use cichlid::{prelude::*, ColorRGB};
+//! use cichlid::matrix::{Layout, Matrix};
+//!
+//! let mut leds = [ColorRGB::Black; 16 * 8];
+//! let mut matrix = Matrix::new(&mut leds, 16, 8, Layout::Serpentine);
+//!
+//! matrix.set(3, 1, ColorRGB::Red);
+//! assert_eq!(matrix.get(3, 1), Some(ColorRGB::Red));
+//! assert_eq!(matrix.xy(3, 1), Some(28)); // Odd rows run backwards.
+//!
+//! matrix.row_mut(7).rainbow_fill_single_cycle(0);
+//! matrix.column_mut(0).gradient_fill_rgb_to_inclusive(ColorRGB::Blue, ColorRGB::Green);
+//! ```
+//!
+//! [`Matrix`]: ./struct.Matrix.html
+//! [`Layout`]: ./enum.Layout.html
+//! [`Orientation`]: ./enum.Orientation.html
+//! [`prelude`]: ../prelude/index.html
+
+#[cfg(feature = "no-std")]
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+#[cfg(not(feature = "no-std"))]
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+
+#[cfg(feature = "no-std")]
+use core::marker::PhantomData;
+#[cfg(not(feature = "no-std"))]
+use std::marker::PhantomData;
+
+use crate::ColorRGB;
+
+mod draw;
+mod effects;
+
+pub use self::effects::{ShiftDirection, ShiftEdge};
+
+/// How the pixels of a matrix are wired, from the first pixel of the strip to the last.
+///
+/// Each layout starts at the top left corner, `(0, 0)`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Layout<'a> {
+    /// Each row runs left to right, from the top row to the bottom row.
+    RowMajor,
+    /// Rows alternate direction, with even rows running left to right and odd rows running
+    /// right to left. Also known as a zigzag layout.
+    Serpentine,
+    /// Each column runs top to bottom, from the leftmost column to the rightmost column.
+    ColumnMajor,
+    /// Columns alternate direction, with even columns running top to bottom and odd columns
+    /// running bottom to top.
+    VerticalSerpentine,
+    /// Arbitrary wiring, given by a table of strip indices for each `(x, y)` in row major
+    /// order.
+    Lookup(LookupTable<'a>),
+}
+
+/// A transformation applied to coordinates before they are mapped through a `Layout`.
+///
+/// This allows for panels mounted in a different orientation than they are wired. The
+/// rotations are clockwise, and the width and height of rotated and transposed matrices
+/// are swapped.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub enum Orientation {
+    /// Coordinates are used as is.
+    #[default]
+    Normal,
+    /// Rotated 90 degrees clockwise.
+    Rotate90,
+    /// Rotated 180 degrees.
+    Rotate180,
+    /// Rotated 270 degrees clockwise.
+    Rotate270,
+    /// Mirrored from left to right.
+    FlipHorizontal,
+    /// Mirrored from top to bottom.
+    FlipVertical,
+    /// Mirrored along the diagonal from the top left to the bottom right.
+    Transpose,
+    /// Mirrored along the diagonal from the top right to the bottom left.
+    AntiTranspose,
+}
+
+impl Orientation {
+    /// Returns if the orientation swaps the width and height.
+    #[inline]
+    pub const fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Orientation::Rotate90
+                | Orientation::Rotate270
+                | Orientation::Transpose
+                | Orientation::AntiTranspose
+        )
+    }
+}
+
+/// A table mapping `(x, y)` coordinates in row major order to indices in a strip.
+///
+/// Every index in the table must be unique, which is checked once upon creation. This allows
+/// the table to be reused for each `Matrix` without being checked again.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::ColorRGB;
+/// use cichlid::matrix::{Layout, LookupTable, Matrix};
+///
+/// // A 2x2 panel wired counter-clockwise from the top left.
+/// static TABLE: [u16; 4] = [0, 3, 1, 2];
+/// let table = LookupTable::new(&TABLE).unwrap();
+///
+/// let mut leds = [ColorRGB::Black; 4];
+/// let mut matrix = Matrix::new(&mut leds, 2, 2, Layout::Lookup(table));
+/// matrix.set(1, 0, ColorRGB::White);
+/// assert_eq!(leds[3], ColorRGB::White);
+///
+/// assert!(LookupTable::new(&[0, 1, 1, 2]).is_none());
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct LookupTable<'a> {
+    table: &'a [u16],
+    strip_len: usize,
+}
+
+impl<'a> LookupTable<'a> {
+    /// Creates a new `LookupTable`, returning `None` if any index is repeated.
+    pub const fn new(table: &'a [u16]) -> Option<Self> {
+        let mut strip_len: usize = 0;
+        let mut i: usize = 0;
+        while i < table.len() {
+            let mut j: usize = i + 1;
+            while j < table.len() {
+                if table[i] == table[j] {
+                    return None;
+                }
+                j += 1;
+            }
+            if table[i] as usize >= strip_len {
+                strip_len = table[i] as usize + 1;
+            }
+            i += 1;
+        }
+        Some(LookupTable { table, strip_len })
+    }
+
+    /// Returns the table of indices.
+    #[inline(always)]
+    pub const fn table(&self) -> &'a [u16] {
+        self.table
+    }
+
+    /// Returns the minimum length of strip the table can index into.
+    #[inline(always)]
+    pub const fn strip_len(&self) -> usize {
+        self.strip_len
+    }
+}
+
+/// The parts of a `Matrix` used to map coordinates to indices.
+#[derive(Copy, Clone, Debug)]
+struct Mapping<'a> {
+    width: usize,
+    height: usize,
+    layout: Layout<'a>,
+    orientation: Orientation,
+}
+
+impl<'a> Mapping<'a> {
+    /// Width after the orientation is applied.
+    #[inline(always)]
+    fn width(&self) -> usize {
+        match self.orientation.swaps_axes() {
+            true => self.height,
+            false => self.width,
+        }
+    }
+
+    /// Height after the orientation is applied.
+    #[inline(always)]
+    fn height(&self) -> usize {
+        match self.orientation.swaps_axes() {
+            true => self.width,
+            false => self.height,
+        }
+    }
+
+    /// Maps in bounds coordinates to an index.
+    #[inline]
+    fn index(&self, x: usize, y: usize) -> usize {
+        let (w, h) = (self.width, self.height);
+        let (x, y) = match self.orientation {
+            Orientation::Normal => (x, y),
+            Orientation::Rotate90 => (y, h - 1 - x),
+            Orientation::Rotate180 => (w - 1 - x, h - 1 - y),
+            Orientation::Rotate270 => (w - 1 - y, x),
+            Orientation::FlipHorizontal => (w - 1 - x, y),
+            Orientation::FlipVertical => (x, h - 1 - y),
+            Orientation::Transpose => (y, x),
+            Orientation::AntiTranspose => (w - 1 - y, h - 1 - x),
+        };
+        match self.layout {
+            Layout::RowMajor => y * w + x,
+            Layout::Serpentine if y & 1 == 1 => y * w + (w - 1 - x),
+            Layout::Serpentine => y * w + x,
+            Layout::ColumnMajor => x * h + y,
+            Layout::VerticalSerpentine if x & 1 == 1 => x * h + (h - 1 - y),
+            Layout::VerticalSerpentine => x * h + y,
+            Layout::Lookup(lookup) => lookup.table[y * w + x] as usize,
+        }
+    }
+}
+
+/// A two dimensional view over a strip of `ColorRGB`s.
+///
+/// See the [module level documentation](./index.html) for more.
+#[derive(Debug)]
+pub struct Matrix<'a> {
+    pixels: &'a mut [ColorRGB],
+    mapping: Mapping<'a>,
+}
+
+impl<'a> Matrix<'a> {
+    /// Creates a new `Matrix` view over `pixels`, wired as `width` by `height` pixels in the
+    /// given `layout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` is too short to hold every pixel of the matrix, or if a lookup
+    /// table does not have exactly `width * height` indices.
+    pub fn new(
+        pixels: &'a mut [ColorRGB],
+        width: usize,
+        height: usize,
+        layout: Layout<'a>,
+    ) -> Self {
+        let needed: usize = match layout {
+            Layout::Lookup(lookup) => {
+                assert_eq!(
+                    lookup.table.len(),
+                    width * height,
+                    "lookup table does not match matrix size"
+                );
+                lookup.strip_len
+            }
+            _ => width * height,
+        };
+        assert!(pixels.len() >= needed, "matrix is larger than its pixels");
+        Matrix {
+            pixels,
+            mapping: Mapping {
+                width,
+                height,
+                layout,
+                orientation: Orientation::Normal,
+            },
+        }
+    }
+
+    /// Applies an `Orientation` to the matrix.
+    #[inline]
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.mapping.orientation = orientation;
+        self
+    }
+
+    /// Returns the width of the matrix, after the orientation is applied.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.mapping.width()
+    }
+
+    /// Returns the height of the matrix, after the orientation is applied.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.mapping.height()
+    }
+
+    /// Returns the layout of the matrix.
+    #[inline]
+    pub fn layout(&self) -> Layout<'a> {
+        self.mapping.layout
+    }
+
+    /// Returns the orientation of the matrix.
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        self.mapping.orientation
+    }
+
+    /// Returns the underlying strip of pixels.
+    #[inline]
+    pub fn pixels(&self) -> &[ColorRGB] {
+        self.pixels
+    }
+
+    /// Returns the underlying strip of pixels mutably.
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [ColorRGB] {
+        self.pixels
+    }
+
+    /// Returns the index into the strip of the pixel at `(x, y)`, or `None` if the
+    /// coordinates are out of bounds.
+    #[inline]
+    pub fn xy(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width() && y < self.height() {
+            Some(self.mapping.index(x, y))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the color at `(x, y)`, or `None` if the coordinates are out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<ColorRGB> {
+        self.xy(x, y).map(|i| self.pixels[i])
+    }
+
+    /// Returns a mutable reference to the color at `(x, y)`, or `None` if the coordinates
+    /// are out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut ColorRGB> {
+        match self.xy(x, y) {
+            Some(i) => Some(&mut self.pixels[i]),
+            None => None,
+        }
+    }
+
+    /// Sets the color at `(x, y)`. Out of bounds coordinates are ignored.
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, color: ColorRGB) {
+        if let Some(pixel) = self.get_mut(x, y) {
+            *pixel = color;
+        }
+    }
+
+    /// Returns an iterator over the row `y` from left to right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    #[inline]
+    pub fn row_mut(&mut self, y: usize) -> LineMut<'_> {
+        assert!(y < self.height(), "row out of bounds");
+        LineMut::new(self, y, true)
+    }
+
+    /// Returns an iterator over the column `x` from top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is out of bounds.
+    #[inline]
+    pub fn column_mut(&mut self, x: usize) -> LineMut<'_> {
+        assert!(x < self.width(), "column out of bounds");
+        LineMut::new(self, x, false)
+    }
+}
+
+/// Mutable iterator over a single row or column of a `Matrix`.
+///
+/// Created by `Matrix::row_mut()` and `Matrix::column_mut()`.
+#[derive(Debug)]
+pub struct LineMut<'m> {
+    pixels: *mut ColorRGB,
+    mapping: Mapping<'m>,
+    fixed: usize,
+    is_row: bool,
+    front: usize,
+    back: usize,
+    _marker: PhantomData<&'m mut ColorRGB>,
+}
+
+impl<'m> LineMut<'m> {
+    fn new<'a: 'm>(matrix: &'m mut Matrix<'a>, fixed: usize, is_row: bool) -> Self {
+        let back = match is_row {
+            true => matrix.width(),
+            false => matrix.height(),
+        };
+        LineMut {
+            pixels: matrix.pixels.as_mut_ptr(),
+            mapping: matrix.mapping,
+            fixed,
+            is_row,
+            front: 0,
+            back,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn pixel(&mut self, pos: usize) -> &'m mut ColorRGB {
+        let idx: usize = match self.is_row {
+            true => self.mapping.index(pos, self.fixed),
+            false => self.mapping.index(self.fixed, pos),
+        };
+        // Safety: Every layout maps distinct coordinates to distinct indices (lookup tables
+        // are checked for this when created), and each position is only yielded once. Every
+        // index was checked to be in bounds of the pixels when the `Matrix` was created.
+        unsafe { &mut *self.pixels.add(idx) }
+    }
+}
+
+impl<'m> Iterator for LineMut<'m> {
+    type Item = &'m mut ColorRGB;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'m mut ColorRGB> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.pixel(self.front - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'m> DoubleEndedIterator for LineMut<'m> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'m mut ColorRGB> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.pixel(self.back))
+    }
+}
+
+impl<'m> ExactSizeIterator for LineMut<'m> {}
+
+impl<'m> FusedIterator for LineMut<'m> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+    use crate::HSV;
+
+    fn indices(matrix: &Matrix) -> [[usize; 3]; 2] {
+        let mut out = [[0; 3]; 2];
+        for (y, row) in out.iter_mut().enumerate() {
+            for (x, i) in row.iter_mut().enumerate() {
+                *i = matrix.xy(x, y).unwrap();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn layouts() {
+        let mut leds = [ColorRGB::Black; 6];
+        let expected = [
+            (Layout::RowMajor, [[0, 1, 2], [3, 4, 5]]),
+            (Layout::Serpentine, [[0, 1, 2], [5, 4, 3]]),
+            (Layout::ColumnMajor, [[0, 2, 4], [1, 3, 5]]),
+            (Layout::VerticalSerpentine, [[0, 3, 4], [1, 2, 5]]),
+        ];
+        for (layout, idx) in expected.iter() {
+            let matrix = Matrix::new(&mut leds, 3, 2, *layout);
+            assert_eq!(&indices(&matrix), idx, "{:?}", layout);
+            assert_eq!(matrix.xy(3, 0), None);
+            assert_eq!(matrix.xy(0, 2), None);
+        }
+
+        let table = LookupTable::new(&[5, 0, 4, 1, 3, 2]).unwrap();
+        let matrix = Matrix::new(&mut leds, 3, 2, Layout::Lookup(table));
+        assert_eq!(indices(&matrix), [[5, 0, 4], [1, 3, 2]]);
+    }
+
+    #[test]
+    fn orientations() {
+        let mut leds = [ColorRGB::Black; 6];
+        // Physically 2 wide and 3 tall, viewed as 3 wide and 2 tall when the axes swap.
+        let expected = [
+            (Orientation::Rotate90, [[4, 2, 0], [5, 3, 1]]),
+            (Orientation::Rotate270, [[1, 3, 5], [0, 2, 4]]),
+            (Orientation::Transpose, [[0, 2, 4], [1, 3, 5]]),
+            (Orientation::AntiTranspose, [[5, 3, 1], [4, 2, 0]]),
+        ];
+        for (orientation, idx) in expected.iter() {
+            let matrix =
+                Matrix::new(&mut leds, 2, 3, Layout::RowMajor).with_orientation(*orientation);
+            assert_eq!((matrix.width(), matrix.height()), (3, 2));
+            assert_eq!(&indices(&matrix), idx, "{:?}", orientation);
+        }
+
+        let expected = [
+            (Orientation::Normal, [[0, 1, 2], [3, 4, 5]]),
+            (Orientation::Rotate180, [[5, 4, 3], [2, 1, 0]]),
+            (Orientation::FlipHorizontal, [[2, 1, 0], [5, 4, 3]]),
+            (Orientation::FlipVertical, [[3, 4, 5], [0, 1, 2]]),
+        ];
+        for (orientation, idx) in expected.iter() {
+            let matrix =
+                Matrix::new(&mut leds, 3, 2, Layout::RowMajor).with_orientation(*orientation);
+            assert_eq!(&indices(&matrix), idx, "{:?}", orientation);
+        }
+    }
+
+    #[test]
+    fn lookup_table_validation() {
+        assert!(LookupTable::new(&[]).is_some());
+        assert!(LookupTable::new(&[3, 2, 3]).is_none());
+        assert_eq!(LookupTable::new(&[0, 9, 4]).unwrap().strip_len(), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lookup_table_out_of_bounds() {
+        let mut leds = [ColorRGB::Black; 3];
+        let table = LookupTable::new(&[0, 1, 2, 3]).unwrap();
+        Matrix::new(&mut leds, 2, 2, Layout::Lookup(table));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut leds = [ColorRGB::Black; 20];
+        let mut matrix = Matrix::new(&mut leds, 5, 4, Layout::Serpentine);
+        matrix
+            .row_mut(1)
+            .gradient_fill_rgb_to_inclusive(ColorRGB::Red, ColorRGB::Blue);
+        assert_eq!(matrix.get(0, 1), Some(ColorRGB::Red));
+        assert_eq!(matrix.get(4, 1), Some(ColorRGB::Blue));
+        assert_eq!(matrix.pixels()[5], ColorRGB::Blue);
+
+        matrix.column_mut(2).rainbow_fill(0, 64 << 8);
+        for y in 0..4 {
+            let hue = (y * 64) as u8;
+            assert_eq!(
+                matrix.get(2, y),
+                Some(ColorRGB::from(HSV::new(hue, 255, 255)))
+            );
+        }
+
+        let mut column = matrix.column_mut(4);
+        assert_eq!(column.len(), 4);
+        column.next_back().unwrap().clear();
+        assert_eq!(column.len(), 3);
+    }
+}
@@ -0,0 +1,403 @@
+//! Parsing colors from strings.
+
+#[cfg(feature = "no-std")]
+use core::{fmt, str::FromStr};
+#[cfg(not(feature = "no-std"))]
+use std::{fmt, str::FromStr};
+
+use crate::{ColorRGB, HSV};
+
+/// The reason a color string could not be parsed.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum ParseColorErrorKind {
+    /// The string was empty.
+    Empty,
+    /// The string isn't in a known format, and isn't the name of a color.
+    UnknownFormat,
+    /// A hexadecimal color has the wrong number of digits, or a character that isn't a digit.
+    InvalidHex,
+    /// An argument of a function such as `rgb()` isn't a valid number.
+    InvalidNumber,
+    /// An argument of a function such as `rgb()` is out of range.
+    OutOfRange,
+    /// A function such as `rgb()` doesn't have exactly three arguments.
+    WrongArgumentCount,
+}
+
+/// Error returned when parsing a `ColorRGB` or `HSV` from a string fails.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ParseColorError {
+    kind: ParseColorErrorKind,
+}
+
+impl ParseColorError {
+    #[inline(always)]
+    const fn new(kind: ParseColorErrorKind) -> Self {
+        ParseColorError { kind }
+    }
+
+    /// Returns the reason the color could not be parsed.
+    #[inline(always)]
+    pub fn kind(&self) -> ParseColorErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let desc = match self.kind {
+            ParseColorErrorKind::Empty => "cannot parse color from empty string",
+            ParseColorErrorKind::UnknownFormat => "unknown color format or name",
+            ParseColorErrorKind::InvalidHex => "invalid hexadecimal color",
+            ParseColorErrorKind::InvalidNumber => "invalid number in color",
+            ParseColorErrorKind::OutOfRange => "number in color out of range",
+            ParseColorErrorKind::WrongArgumentCount => "expected three arguments",
+        };
+        f.write_str(desc)
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+impl std::error::Error for ParseColorError {}
+
+/// A color as written in a string, before converting to the requested type.
+enum Parsed {
+    Rgb(ColorRGB),
+    /// A `hsv()` color, along with its exact RGB value on the spectrum color wheel.
+    Hsv(HSV, ColorRGB),
+}
+
+/// Strips `prefix` from the start of `s`, ignoring case.
+fn strip_prefix_ignore_case<'s>(s: &'s str, prefix: &str) -> Option<&'s str> {
+    let head = s.get(..prefix.len())?;
+    match head.eq_ignore_ascii_case(prefix) {
+        true => Some(&s[prefix.len()..]),
+        false => None,
+    }
+}
+
+/// Parses the digits of a hexadecimal color, either `rgb` or `rrggbb`.
+fn parse_hex(digits: &str, allow_short: bool) -> Result<ColorRGB, ParseColorError> {
+    let invalid = ParseColorError::new(ParseColorErrorKind::InvalidHex);
+    let mut code: u32 = 0;
+    for c in digits.chars() {
+        code = (code << 4) | c.to_digit(16).ok_or(invalid)?;
+    }
+    match digits.len() {
+        6 => Ok(ColorRGB::from_color_code(code)),
+        3 if allow_short => {
+            let expand = |nibble: u32| (nibble & 0xF) as u8 * 17;
+            Ok(ColorRGB::new(
+                expand(code >> 8),
+                expand(code >> 4),
+                expand(code),
+            ))
+        }
+        _ => Err(invalid),
+    }
+}
+
+/// Splits the three comma separated arguments of a function such as `rgb()`, if `s` is a call
+/// to `name`.
+fn function_args<'s>(s: &'s str, name: &str) -> Option<Result<[&'s str; 3], ParseColorError>> {
+    let rest = strip_prefix_ignore_case(s, name)?.trim_start();
+    let inner = rest.strip_prefix('(')?;
+    let inner = match inner.strip_suffix(')') {
+        Some(inner) => inner,
+        None => {
+            return Some(Err(ParseColorError::new(
+                ParseColorErrorKind::UnknownFormat,
+            )))
+        }
+    };
+    let mut args = [""; 3];
+    let mut count: usize = 0;
+    for arg in inner.split(',') {
+        if count == 3 {
+            return Some(Err(ParseColorError::new(
+                ParseColorErrorKind::WrongArgumentCount,
+            )));
+        }
+        args[count] = arg.trim();
+        count += 1;
+    }
+    match count {
+        3 => Some(Ok(args)),
+        _ => Some(Err(ParseColorError::new(
+            ParseColorErrorKind::WrongArgumentCount,
+        ))),
+    }
+}
+
+/// Parses an integer from `min` to `max` (inclusive).
+fn parse_int(arg: &str, min: i32, max: i32) -> Result<i32, ParseColorError> {
+    let value: i32 = arg
+        .parse()
+        .map_err(|_| ParseColorError::new(ParseColorErrorKind::InvalidNumber))?;
+    match value >= min && value <= max {
+        true => Ok(value),
+        false => Err(ParseColorError::new(ParseColorErrorKind::OutOfRange)),
+    }
+}
+
+/// Parses a hue in degrees, wrapping around to `0..360`.
+fn parse_degrees(arg: &str) -> Result<i32, ParseColorError> {
+    let arg = arg.strip_suffix("deg").unwrap_or(arg);
+    Ok(parse_int(arg, i32::MIN, i32::MAX)?.rem_euclid(360))
+}
+
+/// Parses a percentage from `0` to `100`, with or without a trailing `%`.
+fn parse_percent(arg: &str) -> Result<i32, ParseColorError> {
+    parse_int(arg.strip_suffix('%').unwrap_or(arg), 0, 100)
+}
+
+/// Converts a hue in degrees, with saturation and lightness in percentages, to RGB.
+fn hsl_to_rgb(h: i32, s: i32, l: i32) -> ColorRGB {
+    // Chroma, intermediate, and minimum components, out of 10000.
+    let c: i32 = (100 - (2 * l - 100).abs()) * s;
+    let x: i32 = c * (60 - (h % 120 - 60).abs()) / 60;
+    let m: i32 = l * 100 - c / 2;
+    let (r, g, b) = match h / 60 {
+        0 => (c, x, 0),
+        1 => (x, c, 0),
+        2 => (0, c, x),
+        3 => (0, x, c),
+        4 => (x, 0, c),
+        _ => (c, 0, x),
+    };
+    let channel = |v: i32| (((v + m) * 255 + 5000) / 10000) as u8;
+    ColorRGB::new(channel(r), channel(g), channel(b))
+}
+
+/// Converts a hue in degrees, with saturation and value in percentages, to RGB.
+fn hsv_to_rgb(h: i32, s: i32, v: i32) -> ColorRGB {
+    // Chroma, intermediate, and minimum components, out of 10000.
+    let c: i32 = v * s;
+    let x: i32 = c * (60 - (h % 120 - 60).abs()) / 60;
+    let m: i32 = v * 100 - c;
+    let (r, g, b) = match h / 60 {
+        0 => (c, x, 0),
+        1 => (x, c, 0),
+        2 => (0, c, x),
+        3 => (0, x, c),
+        4 => (x, 0, c),
+        _ => (c, 0, x),
+    };
+    let channel = |v: i32| (((v + m) * 255 + 5000) / 10000) as u8;
+    ColorRGB::new(channel(r), channel(g), channel(b))
+}
+
+/// Converts a percentage to a fraction out of `255`.
+#[inline]
+fn percent_u8(p: i32) -> u8 {
+    ((p * 255 + 50) / 100) as u8
+}
+
+/// Parses a color in any of the supported formats.
+fn parse(s: &str) -> Result<Parsed, ParseColorError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseColorError::new(ParseColorErrorKind::Empty));
+    }
+    if let Some(digits) = s.strip_prefix('#') {
+        return parse_hex(digits, true).map(Parsed::Rgb);
+    }
+    if let Some(digits) = strip_prefix_ignore_case(s, "0x") {
+        return parse_hex(digits, false).map(Parsed::Rgb);
+    }
+    if let Some(args) = function_args(s, "rgb") {
+        let [r, g, b] = args?;
+        let (r, g, b) = (
+            parse_int(r, 0, 255)?,
+            parse_int(g, 0, 255)?,
+            parse_int(b, 0, 255)?,
+        );
+        return Ok(Parsed::Rgb(ColorRGB::new(r as u8, g as u8, b as u8)));
+    }
+    if let Some(args) = function_args(s, "hsl") {
+        let [h, s, l] = args?;
+        let color = hsl_to_rgb(parse_degrees(h)?, parse_percent(s)?, parse_percent(l)?);
+        return Ok(Parsed::Rgb(color));
+    }
+    if let Some(args) = function_args(s, "hsv") {
+        let [h, s, v] = args?;
+        let (h, s, v) = (parse_degrees(h)?, parse_percent(s)?, parse_percent(v)?);
+        let hue: u8 = ((h * 256 + 180) / 360) as u8;
+        let hsv = HSV::new(hue, percent_u8(s), percent_u8(v));
+        return Ok(Parsed::Hsv(hsv, hsv_to_rgb(h, s, v)));
+    }
+    ColorRGB::from_name(s)
+        .map(Parsed::Rgb)
+        .ok_or(ParseColorError::new(ParseColorErrorKind::UnknownFormat))
+}
+
+impl FromStr for ColorRGB {
+    type Err = ParseColorError;
+
+    /// Parses a color from a string.
+    ///
+    /// The following formats are supported, ignoring case and surrounding whitespace:
+    ///
+    /// - `#rgb` and `#rrggbb` hexadecimal colors, as well as `0xRRGGBB`.
+    /// - `rgb(r, g, b)`, with each component from `0` to `255`.
+    /// - `hsl(h, s%, l%)`, with the hue in degrees, as in CSS.
+    /// - `hsv(h, s%, v%)`, with the hue in degrees on the same color wheel as `hsl()`.
+    /// - Any name from the `color_codes` module, such as `"DarkOrange"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{ColorRGB, ParseColorErrorKind};
+    ///
+    /// assert_eq!("#F80".parse(), Ok(ColorRGB::new(0xFF, 0x88, 0x00)));
+    /// assert_eq!("0x12ab00".parse(), Ok(ColorRGB::new(0x12, 0xAB, 0x00)));
+    /// assert_eq!("rgb(10, 20, 30)".parse(), Ok(ColorRGB::new(10, 20, 30)));
+    /// assert_eq!("hsl(120, 100%, 50%)".parse(), Ok(ColorRGB::new(0, 255, 0)));
+    /// assert_eq!("hsv(120, 100%, 100%)".parse(), Ok(ColorRGB::new(0, 255, 0)));
+    /// assert_eq!("darkorange".parse(), Ok(ColorRGB::DarkOrange));
+    ///
+    /// let err = "rgb(10, 20, 300)".parse::<ColorRGB>().unwrap_err();
+    /// assert_eq!(err.kind(), ParseColorErrorKind::OutOfRange);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse(s)? {
+            Parsed::Rgb(rgb) => Ok(rgb),
+            Parsed::Hsv(_, rgb) => Ok(rgb),
+        }
+    }
+}
+
+impl FromStr for HSV {
+    type Err = ParseColorError;
+
+    /// Parses a color from a string, in any of the formats supported by `ColorRGB`.
+    ///
+    /// `hsv(h, s%, v%)` colors are parsed exactly, while every other format is converted from
+    /// RGB with a mathematical (spectrum) color wheel. Either way, the hue is on the spectrum
+    /// wheel, so the parsed color is rendered by `HSV::to_rgb_spectrum_full()`. Converting it
+    /// with `ColorRGB::from()` renders it as a rainbow instead, which gives a different color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{ColorRGB, HSV};
+    ///
+    /// assert_eq!("hsv(180, 100%, 50%)".parse(), Ok(HSV::new(128, 255, 128)));
+    ///
+    /// let green: HSV = "#00FF00".parse().unwrap();
+    /// assert_eq!(green, HSV::new(85, 255, 255));
+    /// assert_eq!(green.to_rgb_spectrum_full(), ColorRGB::new(2, 255, 0));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse(s)? {
+            Parsed::Rgb(rgb) => Ok(HSV::from_rgb_spectrum(rgb)),
+            Parsed::Hsv(hsv, _) => Ok(hsv),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kind<T: FromStr<Err = ParseColorError>>(s: &str) -> ParseColorErrorKind {
+        match s.parse::<T>() {
+            Ok(_) => panic!("{:?} parsed", s),
+            Err(err) => err.kind(),
+        }
+    }
+
+    #[test]
+    fn formats() {
+        let parse = |s: &str| s.parse::<ColorRGB>().unwrap();
+        assert_eq!(parse("#abc"), ColorRGB::new(0xAA, 0xBB, 0xCC));
+        assert_eq!(parse("  #A0b1C2 "), ColorRGB::new(0xA0, 0xB1, 0xC2));
+        assert_eq!(parse("0XFFFFFF"), ColorRGB::White);
+        assert_eq!(parse("RGB( 1,2 , 3 )"), ColorRGB::new(1, 2, 3));
+        assert_eq!(parse("hsl(0, 100%, 50%)"), ColorRGB::new(255, 0, 0));
+        assert_eq!(parse("hsl(240deg, 100, 25)"), ColorRGB::new(0, 0, 128));
+        assert_eq!(parse("hsl(-60, 50%, 50%)"), ColorRGB::new(191, 64, 191));
+        assert_eq!(parse("hsl(30, 0%, 100%)"), ColorRGB::White);
+        assert_eq!(parse("hsv(0, 0%, 100%)"), ColorRGB::White);
+        assert_eq!(parse("hsv(360, 100%, 100%)"), ColorRGB::Red);
+        assert_eq!(parse("hsv(210, 67%, 60%)"), ColorRGB::new(50, 102, 153));
+        // The same CSS hue gives the same color with either function.
+        for h in (0..360).step_by(7) {
+            for &(s, l) in [(100, 50), (50, 40), (20, 80)].iter() {
+                let v: i32 = l + s * l.min(100 - l) / 100;
+                let s_v: i32 = match v {
+                    0 => 0,
+                    v => (200 * (v - l) + v / 2) / v,
+                };
+                let (hsl, hsv) = (hsl_to_rgb(h, s, l), hsv_to_rgb(h, s_v, v));
+                for i in 0..3 {
+                    assert!((i16::from(hsl[i]) - i16::from(hsv[i])).abs() <= 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn names() {
+        for &(name, color) in crate::color_codes::NAMED_COLORS.iter() {
+            assert_eq!(name.parse(), Ok(color));
+            assert_eq!(name.to_ascii_lowercase().parse(), Ok(color));
+            assert_eq!(name.to_ascii_uppercase().parse(), Ok(color));
+        }
+    }
+
+    #[test]
+    fn hsv() {
+        assert_eq!("hsv(90, 50%, 20%)".parse(), Ok(HSV::new(64, 128, 51)));
+        assert_eq!("red".parse(), Ok(HSV::new(0, 255, 255)));
+        assert_eq!("#0000FF".parse(), Ok(HSV::new(171, 255, 255)));
+        assert_eq!("#808080".parse(), Ok(HSV::new(0, 0, 128)));
+        assert_eq!("black".parse(), Ok(HSV::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn hsv_renders_on_spectrum() {
+        let inputs = [
+            "#00ff00",
+            "#123456",
+            "DarkOrange",
+            "rgb(200, 10, 90)",
+            "hsl(300, 40%, 70%)",
+            "hsv(120, 100%, 100%)",
+            "hsv(45, 80%, 90%)",
+        ];
+        for s in inputs.iter() {
+            let rgb: ColorRGB = s.parse().unwrap();
+            let hsv: HSV = s.parse().unwrap();
+            // With only 256 hues, saturated colors can be off by a couple of steps.
+            let spectrum = hsv.to_rgb_spectrum_full();
+            for i in 0..3 {
+                assert!(
+                    (i16::from(spectrum[i]) - i16::from(rgb[i])).abs() <= 4,
+                    "{}",
+                    s
+                );
+            }
+        }
+        // The rainbow places green elsewhere, so it can't render a parsed `HSV`.
+        let green: HSV = "#00ff00".parse().unwrap();
+        assert_ne!(ColorRGB::from(green), ColorRGB::Lime);
+    }
+
+    #[test]
+    fn errors() {
+        use super::ParseColorErrorKind::*;
+        assert_eq!(kind::<ColorRGB>(" "), Empty);
+        assert_eq!(kind::<ColorRGB>("notacolor"), UnknownFormat);
+        assert_eq!(kind::<ColorRGB>("rgb(1, 2, 3"), UnknownFormat);
+        assert_eq!(kind::<ColorRGB>("#12345"), InvalidHex);
+        assert_eq!(kind::<ColorRGB>("0xFFF"), InvalidHex);
+        assert_eq!(kind::<ColorRGB>("#GGGGGG"), InvalidHex);
+        assert_eq!(kind::<ColorRGB>("#ÿÿ"), InvalidHex);
+        assert_eq!(kind::<ColorRGB>("rgb(1, 2)"), WrongArgumentCount);
+        assert_eq!(kind::<ColorRGB>("rgb(1, 2, 3, 4)"), WrongArgumentCount);
+        assert_eq!(kind::<ColorRGB>("rgb(1, x, 3)"), InvalidNumber);
+        assert_eq!(kind::<HSV>("hsl(0, 101%, 50%)"), OutOfRange);
+        assert_eq!(kind::<HSV>("rgb(-1, 0, 0)"), OutOfRange);
+    }
+}
@@ -0,0 +1,233 @@
+//! Color difference (delta E) metrics in the `Lab` space.
+
+use super::{float, Lab};
+
+/// `25^7`, used by the chroma compensation of CIEDE2000.
+const POW25_7: f32 = 6_103_515_625.0;
+
+/// Raises `x` to the power of seven.
+#[inline(always)]
+fn pow7(x: f32) -> f32 {
+    let x3: f32 = x * x * x;
+    x3 * x3 * x
+}
+
+/// Hue angle in degrees, from `0.0` up to `360.0`.
+#[inline(always)]
+fn hue_degrees(b: f32, a: f32) -> f32 {
+    if a == 0.0 && b == 0.0 {
+        return 0.0;
+    }
+    let h: f32 = float::atan2(b, a).to_degrees();
+    if h < 0.0 {
+        h + 360.0
+    } else {
+        h
+    }
+}
+
+impl Lab {
+    /// Returns the CIE76 color difference to another color, which is the euclidean distance
+    /// between the two.
+    ///
+    /// A difference of around `2.3` is just noticeable. This is fast to compute, but
+    /// exaggerates differences between saturated colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::perceptual::Lab;
+    ///
+    /// let a = Lab::new(50.0, 0.0, 0.0);
+    /// assert_eq!(a.delta_e76(Lab::new(53.0, 4.0, 0.0)), 5.0);
+    /// ```
+    pub fn delta_e76(self, other: Lab) -> f32 {
+        let (dl, da, db) = (self.l - other.l, self.a - other.a, self.b - other.b);
+        float::sqrt(dl * dl + da * da + db * db)
+    }
+
+    /// Returns the CIEDE2000 color difference to another color.
+    ///
+    /// This corrects CIE76 to closely match how differently two colors are perceived,
+    /// especially for saturated colors and blues, where a difference of `1.0` is about the
+    /// smallest that can be noticed. It's far slower to compute than `delta_e76()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    /// use cichlid::perceptual::Lab;
+    ///
+    /// // CIE76 greatly overstates the difference between these two blues.
+    /// let a = Lab::from(ColorRGB::new(0, 0, 255));
+    /// let b = Lab::from(ColorRGB::new(0, 0, 225));
+    /// assert!(a.delta_e76(b) > 12.0);
+    /// assert!(a.delta_e2000(b) < 4.0);
+    /// ```
+    pub fn delta_e2000(self, other: Lab) -> f32 {
+        let (l1, a1, b1) = (self.l, self.a, self.b);
+        let (l2, a2, b2) = (other.l, other.a, other.b);
+
+        // Scale `a` to compensate for the low chroma of neutral colors.
+        let c_bar: f32 = (float::sqrt(a1 * a1 + b1 * b1) + float::sqrt(a2 * a2 + b2 * b2)) / 2.0;
+        let c_bar7: f32 = pow7(c_bar);
+        let g: f32 = 0.5 * (1.0 - float::sqrt(c_bar7 / (c_bar7 + POW25_7)));
+        let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+
+        let c1: f32 = float::sqrt(a1 * a1 + b1 * b1);
+        let c2: f32 = float::sqrt(a2 * a2 + b2 * b2);
+        let h1: f32 = hue_degrees(b1, a1);
+        let h2: f32 = hue_degrees(b2, a2);
+
+        let delta_l: f32 = l2 - l1;
+        let delta_c: f32 = c2 - c1;
+        let chroma_product: f32 = c1 * c2;
+        let delta_h: f32 = if chroma_product == 0.0 {
+            0.0
+        } else if h2 - h1 > 180.0 {
+            h2 - h1 - 360.0
+        } else if h2 - h1 < -180.0 {
+            h2 - h1 + 360.0
+        } else {
+            h2 - h1
+        };
+        let delta_h: f32 =
+            2.0 * float::sqrt(chroma_product) * float::sin((delta_h / 2.0).to_radians());
+
+        let l_bar: f32 = (l1 + l2) / 2.0;
+        let c_bar: f32 = (c1 + c2) / 2.0;
+        let h_bar: f32 = if chroma_product == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let cos_deg = |deg: f32| float::cos(deg.to_radians());
+        let t: f32 = 1.0 - 0.17 * cos_deg(h_bar - 30.0)
+            + 0.24 * cos_deg(2.0 * h_bar)
+            + 0.32 * cos_deg(3.0 * h_bar + 6.0)
+            - 0.20 * cos_deg(4.0 * h_bar - 63.0);
+
+        let l_offset: f32 = (l_bar - 50.0) * (l_bar - 50.0);
+        let s_l: f32 = 1.0 + 0.015 * l_offset / float::sqrt(20.0 + l_offset);
+        let s_c: f32 = 1.0 + 0.045 * c_bar;
+        let s_h: f32 = 1.0 + 0.015 * c_bar * t;
+
+        let c_bar7: f32 = pow7(c_bar);
+        let r_c: f32 = 2.0 * float::sqrt(c_bar7 / (c_bar7 + POW25_7));
+        let hue_offset: f32 = (h_bar - 275.0) / 25.0;
+        let delta_theta: f32 = 30.0 * float::exp(-hue_offset * hue_offset);
+        let r_t: f32 = -r_c * float::sin((2.0 * delta_theta).to_radians());
+
+        let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+        float::sqrt(l * l + c * c + h * h + r_t * c * h)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn delta_e76() {
+        let a = Lab::new(50.0, 2.6772, -79.7751);
+        assert_eq!(a.delta_e76(a), 0.0);
+        assert!((a.delta_e76(Lab::new(50.0, 0.0, -82.7485)) - 4.0010).abs() < 1e-3);
+        assert_eq!(
+            Lab::new(0.0, 0.0, 0.0).delta_e76(Lab::new(12.0, -3.0, 4.0)),
+            13.0
+        );
+    }
+
+    #[test]
+    fn delta_e2000_reference() {
+        // Test data from Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference Formula:
+        // Implementation Notes, Supplementary Test Data, and Mathematical Observations".
+        let table = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 3.1571, -77.2803), (50.0, 0.0, -82.7485), 2.8615),
+            ((50.0, 2.8361, -74.0200), (50.0, 0.0, -82.7485), 3.4412),
+            ((50.0, -1.3802, -84.2814), (50.0, 0.0, -82.7485), 1.0000),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.4900, -0.0010), (50.0, -2.4900, 0.0009), 7.1792),
+            ((50.0, 2.4900, -0.0010), (50.0, -2.4900, 0.0011), 7.2195),
+            ((50.0, -0.0010, 2.4900), (50.0, 0.0009, -2.4900), 4.8045),
+            ((50.0, 2.5000, 0.0), (50.0, 0.0, -2.5000), 4.3065),
+            ((50.0, 2.5000, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            ((50.0, 2.5000, 0.0), (61.0, -5.0, 29.0), 22.8977),
+            ((50.0, 2.5000, 0.0), (56.0, -27.0, -3.0), 31.9030),
+            ((50.0, 2.5000, 0.0), (58.0, 24.0, 15.0), 19.4535),
+            ((50.0, 2.5000, 0.0), (50.0, 3.1736, 0.5854), 1.0000),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            (
+                (63.0109, -31.0961, -5.8663),
+                (62.8187, -29.7946, -4.0864),
+                1.2630,
+            ),
+            (
+                (61.2901, 3.7196, -5.3901),
+                (61.4292, 2.2480, -4.9620),
+                1.8731,
+            ),
+            (
+                (35.0831, -44.1164, 3.7933),
+                (35.0232, -40.0716, 1.5901),
+                1.8645,
+            ),
+            (
+                (22.7233, 20.0904, -46.6940),
+                (23.0331, 14.9730, -42.5619),
+                2.0373,
+            ),
+            (
+                (36.4612, 47.8580, 18.3852),
+                (36.2715, 50.5065, 21.2231),
+                1.4146,
+            ),
+            (
+                (90.8027, -2.0831, 1.4410),
+                (91.1528, -1.6435, 0.0447),
+                1.4441,
+            ),
+            (
+                (90.9257, -0.5406, -0.9208),
+                (88.6381, -0.8985, -0.7239),
+                1.5381,
+            ),
+            (
+                (6.7747, -0.2908, -2.4247),
+                (5.8714, -0.0985, -2.2286),
+                0.6377,
+            ),
+            (
+                (2.0776, 0.0795, -1.1350),
+                (0.9033, -0.0636, -0.5514),
+                0.9082,
+            ),
+        ];
+        for &((l1, a1, b1), (l2, a2, b2), expected) in table.iter() {
+            let (x, y) = (Lab::new(l1, a1, b1), Lab::new(l2, a2, b2));
+            let (forward, backward) = (x.delta_e2000(y), y.delta_e2000(x));
+            assert!(
+                (forward - expected).abs() < 1e-3,
+                "{} != {}",
+                forward,
+                expected
+            );
+            assert!(
+                (backward - expected).abs() < 1e-3,
+                "{} != {}",
+                backward,
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! Fixed point approximations of the `Lab` and `OKLab` conversions.
+//!
+//! These follow the same steps as the floating point versions, with lookup tables for the
+//! sRGB transfer curve and an integer cube root.
+
+use crate::ColorRGB;
+
+/// Linear light of each sRGB component, where `65535` is full brightness.
+const SRGB_TO_LINEAR: [u16; 256] = [
+    0, 20, 40, 60, 80, 99, 119, 139, 159, 179, 199, 219, 241, 264, 288, 313, 340, 367, 396, 427,
+    458, 491, 526, 562, 599, 637, 677, 718, 761, 805, 851, 898, 947, 997, 1048, 1101, 1156, 1212,
+    1270, 1330, 1391, 1453, 1517, 1583, 1651, 1720, 1790, 1863, 1937, 2013, 2090, 2170, 2250, 2333,
+    2418, 2504, 2592, 2681, 2773, 2866, 2961, 3058, 3157, 3258, 3360, 3464, 3570, 3678, 3788, 3900,
+    4014, 4129, 4247, 4366, 4488, 4611, 4736, 4864, 4993, 5124, 5257, 5392, 5530, 5669, 5810, 5953,
+    6099, 6246, 6395, 6547, 6700, 6856, 7014, 7174, 7335, 7500, 7666, 7834, 8004, 8177, 8352, 8528,
+    8708, 8889, 9072, 9258, 9445, 9635, 9828, 10022, 10219, 10417, 10619, 10822, 11028, 11235,
+    11446, 11658, 11873, 12090, 12309, 12530, 12754, 12980, 13209, 13440, 13673, 13909, 14146,
+    14387, 14629, 14874, 15122, 15371, 15623, 15878, 16135, 16394, 16656, 16920, 17187, 17456,
+    17727, 18001, 18277, 18556, 18837, 19121, 19407, 19696, 19987, 20281, 20577, 20876, 21177,
+    21481, 21787, 22096, 22407, 22721, 23038, 23357, 23678, 24002, 24329, 24658, 24990, 25325,
+    25662, 26001, 26344, 26688, 27036, 27386, 27739, 28094, 28452, 28813, 29176, 29542, 29911,
+    30282, 30656, 31033, 31412, 31794, 32179, 32567, 32957, 33350, 33745, 34143, 34544, 34948,
+    35355, 35764, 36176, 36591, 37008, 37429, 37852, 38278, 38706, 39138, 39572, 40009, 40449,
+    40891, 41337, 41785, 42236, 42690, 43147, 43606, 44069, 44534, 45002, 45473, 45947, 46423,
+    46903, 47385, 47871, 48359, 48850, 49344, 49841, 50341, 50844, 51349, 51858, 52369, 52884,
+    53401, 53921, 54445, 54971, 55500, 56032, 56567, 57105, 57646, 58190, 58737, 59287, 59840,
+    60396, 60955, 61517, 62082, 62650, 63221, 63795, 64372, 64952, 65535,
+];
+
+/// Full brightness in linear light.
+const ONE: i64 = 65535;
+
+/// `6/29`, where the Lab curve switches between linear and cubic, scaled by `ONE`.
+const LAB_DELTA: i64 = 13559;
+
+/// Converts an sRGB component to linear light.
+#[inline(always)]
+fn to_linear(c: u8) -> i64 {
+    i64::from(SRGB_TO_LINEAR[usize::from(c)])
+}
+
+/// Finds the sRGB component closest to a linear light value, clamping it first.
+fn from_linear(v: i64) -> u8 {
+    if v <= 0 {
+        return 0;
+    } else if v >= ONE {
+        return 255;
+    }
+    let v: u16 = v as u16;
+    match SRGB_TO_LINEAR.binary_search(&v) {
+        Ok(i) => i as u8,
+        Err(i) => {
+            if v - SRGB_TO_LINEAR[i - 1] <= SRGB_TO_LINEAR[i] - v {
+                (i - 1) as u8
+            } else {
+                i as u8
+            }
+        }
+    }
+}
+
+/// Multiplies a 3x3 matrix with `1.0` scaled to `16384` by a vector, rounding the result.
+#[inline(always)]
+fn mul_matrix(m: &[[i64; 3]; 3], v: (i64, i64, i64)) -> (i64, i64, i64) {
+    let row = |r: &[i64; 3]| (r[0] * v.0 + r[1] * v.1 + r[2] * v.2 + (1 << 13)) >> 14;
+    (row(&m[0]), row(&m[1]), row(&m[2]))
+}
+
+/// Divides, rounding to the nearest integer.
+#[inline(always)]
+fn div_round(n: i64, d: i64) -> i64 {
+    if n >= 0 {
+        (n + d / 2) / d
+    } else {
+        (n - d / 2) / d
+    }
+}
+
+/// Integer cube root, rounded down.
+fn icbrt(mut x: u64) -> u64 {
+    let mut y: u64 = 0;
+    let mut s: u32 = 63;
+    loop {
+        y *= 2;
+        let b: u64 = 3 * y * (y + 1) + 1;
+        if (x >> s) >= b {
+            x -= b << s;
+            y += 1;
+        }
+        if s == 0 {
+            return y;
+        }
+        s -= 3;
+    }
+}
+
+/// Cube root of a value clamped to `0..=ONE`, where `ONE` is `1.0`.
+#[inline(always)]
+fn cbrt_unit(v: i64) -> i64 {
+    let v: u64 = v.clamp(0, ONE) as u64;
+    icbrt(v * (ONE * ONE) as u64) as i64
+}
+
+/// Cube of a value where `ONE` is `1.0`.
+#[inline(always)]
+fn cube_unit(v: i64) -> i64 {
+    v * v / ONE * v / ONE
+}
+
+/// Linear sRGB to XYZ, with each row divided by the D65 white point.
+const RGB_TO_XYZ: [[i64; 3]; 3] = [[7110, 6164, 3110], [3484, 11717, 1183], [291, 1794, 14299]];
+
+/// XYZ to linear sRGB, with each column multiplied by the D65 white point.
+const XYZ_TO_RGB: [[i64; 3]; 3] = [
+    [50462, -25184, -8894],
+    [-15094, 30737, 741],
+    [867, -3343, 18860],
+];
+
+/// Linear sRGB to LMS cone responses.
+const RGB_TO_LMS: [[i64; 3]; 3] = [[6754, 8787, 843], [3472, 11152, 1760], [1447, 4616, 10321]];
+
+/// Cube roots of LMS to OKLab.
+const LMS_TO_OKLAB: [[i64; 3]; 3] = [
+    [3448, 13003, -67],
+    [32408, -39791, 7383],
+    [424, 12825, -13249],
+];
+
+/// OKLab to cube roots of LMS.
+const OKLAB_TO_LMS: [[i64; 3]; 3] = [
+    [16384, 6494, 3536],
+    [16384, -1730, -1046],
+    [16384, -1466, -21160],
+];
+
+/// LMS cone responses to linear sRGB.
+const LMS_TO_RGB: [[i64; 3]; 3] = [
+    [66793, -54194, 3785],
+    [-20782, 42758, -5592],
+    [-69, -11525, 27978],
+];
+
+/// A fixed point CIE L\*a\*b\* color, computed with integer math only.
+///
+/// Each component is in hundredths of the floating point `Lab`, so `l` runs from `0` to
+/// `10000`.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::ColorRGB;
+/// use cichlid::perceptual::Lab16;
+///
+/// let lab = Lab16::from(ColorRGB::Red);
+/// assert_eq!(lab, Lab16::new(5324, 8011, 6719));
+/// assert_eq!(ColorRGB::from(lab), ColorRGB::Red);
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub struct Lab16 {
+    pub l: i16,
+    pub a: i16,
+    pub b: i16,
+}
+
+impl Lab16 {
+    /// The value of `1.0` in each component.
+    pub const SCALE: i16 = 100;
+
+    /// Creates a new `Lab16`.
+    #[inline(always)]
+    pub const fn new(l: i16, a: i16, b: i16) -> Self {
+        Lab16 { l, a, b }
+    }
+}
+
+/// A fixed point OKLab color, computed with integer math only.
+///
+/// Each component is the floating point `Oklab` multiplied by `16384`, so `l` runs from `0` to
+/// `16384`.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::ColorRGB;
+/// use cichlid::perceptual::Oklab16;
+///
+/// let oklab = Oklab16::from(ColorRGB::White);
+/// assert_eq!(oklab, Oklab16::new(Oklab16::SCALE, 0, 0));
+/// assert_eq!(ColorRGB::from(oklab), ColorRGB::White);
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub struct Oklab16 {
+    pub l: i16,
+    pub a: i16,
+    pub b: i16,
+}
+
+impl Oklab16 {
+    /// The value of `1.0` in each component.
+    pub const SCALE: i16 = 16384;
+
+    /// Creates a new `Oklab16`.
+    #[inline(always)]
+    pub const fn new(l: i16, a: i16, b: i16) -> Self {
+        Oklab16 { l, a, b }
+    }
+}
+
+#[inline(always)]
+fn linear_rgb(color: ColorRGB) -> (i64, i64, i64) {
+    (to_linear(color.r), to_linear(color.g), to_linear(color.b))
+}
+
+#[inline(always)]
+fn srgb(linear: (i64, i64, i64)) -> ColorRGB {
+    ColorRGB::new(
+        from_linear(linear.0),
+        from_linear(linear.1),
+        from_linear(linear.2),
+    )
+}
+
+impl From<ColorRGB> for Lab16 {
+    fn from(color: ColorRGB) -> Self {
+        let f = |t: i64| -> i64 {
+            if t * 29 * 29 * 29 > 216 * ONE {
+                cbrt_unit(t)
+            } else {
+                (24389 * t + 27 * 16 * ONE) / (27 * 116)
+            }
+        };
+        let (x, y, z) = mul_matrix(&RGB_TO_XYZ, linear_rgb(color));
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        Lab16::new(
+            (div_round(11600 * fy, ONE) - 1600) as i16,
+            div_round(50000 * (fx - fy), ONE) as i16,
+            div_round(20000 * (fy - fz), ONE) as i16,
+        )
+    }
+}
+
+impl From<Lab16> for ColorRGB {
+    /// Converts back to sRGB, clamping colors outside of the sRGB gamut.
+    fn from(color: Lab16) -> Self {
+        let finv = |f: i64| -> i64 {
+            if f > LAB_DELTA {
+                cube_unit(f)
+            } else {
+                (116 * f - 16 * ONE) * 27 / 24389
+            }
+        };
+        let fy: i64 = div_round((i64::from(color.l) + 1600) * ONE, 11600);
+        let fx: i64 = fy + div_round(i64::from(color.a) * ONE, 50000);
+        let fz: i64 = fy - div_round(i64::from(color.b) * ONE, 20000);
+        srgb(mul_matrix(&XYZ_TO_RGB, (finv(fx), finv(fy), finv(fz))))
+    }
+}
+
+impl From<ColorRGB> for Oklab16 {
+    fn from(color: ColorRGB) -> Self {
+        let (l, m, s) = mul_matrix(&RGB_TO_LMS, linear_rgb(color));
+        let lms: (i64, i64, i64) = (cbrt_unit(l), cbrt_unit(m), cbrt_unit(s));
+        let (l, a, b) = mul_matrix(&LMS_TO_OKLAB, lms);
+        let scale = |c: i64| div_round(c * i64::from(Oklab16::SCALE), ONE) as i16;
+        Oklab16::new(scale(l), scale(a), scale(b))
+    }
+}
+
+impl From<Oklab16> for ColorRGB {
+    /// Converts back to sRGB, clamping colors outside of the sRGB gamut.
+    fn from(color: Oklab16) -> Self {
+        let unscale = |c: i16| div_round(i64::from(c) * ONE, i64::from(Oklab16::SCALE));
+        let lab: (i64, i64, i64) = (unscale(color.l), unscale(color.a), unscale(color.b));
+        let (l, m, s) = mul_matrix(&OKLAB_TO_LMS, lab);
+        srgb(mul_matrix(
+            &LMS_TO_RGB,
+            (cube_unit(l), cube_unit(m), cube_unit(s)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::perceptual::{Lab, Oklab};
+
+    #[test]
+    fn cube_roots() {
+        for &x in [0u64, 1, 7, 8, 26, 27, 1_000_000, 999_999, u64::MAX].iter() {
+            let y = icbrt(x);
+            assert!(y * y * y <= x);
+            if let Some(next) = (y + 1).checked_pow(3) {
+                assert!(next > x);
+            }
+        }
+        assert_eq!(cbrt_unit(ONE), ONE);
+        assert_eq!(cbrt_unit(0), 0);
+    }
+
+    #[test]
+    fn linear_lookup() {
+        for c in 0..=255 {
+            assert_eq!(from_linear(to_linear(c)), c);
+            assert_eq!(from_linear(to_linear(c) + 1), c);
+        }
+        assert_eq!(from_linear(-100), 0);
+        assert_eq!(from_linear(70000), 255);
+    }
+
+    #[test]
+    fn matches_floating_point() {
+        for code in (0..0xFF_FFFFu32).step_by(0x1357) {
+            let color = ColorRGB::from_color_code(code);
+
+            let lab = Lab16::from(color);
+            let expected = Lab::from(color);
+            assert!((f32::from(lab.l) / 100.0 - expected.l).abs() < 0.1);
+            assert!((f32::from(lab.a) / 100.0 - expected.a).abs() < 0.1);
+            assert!((f32::from(lab.b) / 100.0 - expected.b).abs() < 0.1);
+
+            let oklab = Oklab16::from(color);
+            let expected = Oklab::from(color);
+            assert!((f32::from(oklab.l) / 16384.0 - expected.l).abs() < 0.001);
+            assert!((f32::from(oklab.a) / 16384.0 - expected.a).abs() < 0.001);
+            assert!((f32::from(oklab.b) / 16384.0 - expected.b).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        for code in (0..0xFF_FFFFu32).step_by(0x1357) {
+            let color = ColorRGB::from_color_code(code);
+            for (i, &(a, b)) in [
+                (ColorRGB::from(Lab16::from(color)), color),
+                (ColorRGB::from(Oklab16::from(color)), color),
+            ]
+            .iter()
+            .enumerate()
+            {
+                for c in 0..3 {
+                    assert!(
+                        (i16::from(a[c]) - i16::from(b[c])).abs() <= 1,
+                        "{}: {:?} != {:?}",
+                        i,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+        assert_eq!(Lab16::from(ColorRGB::Black), Lab16::new(0, 0, 0));
+        assert_eq!(Lab16::from(ColorRGB::White), Lab16::new(10000, 0, 0));
+    }
+}
@@ -0,0 +1,99 @@
+//! Floating point functions, from `libm` on `no-std` and the standard library otherwise.
+
+/// Cube root.
+#[cfg(feature = "no-std")]
+#[inline(always)]
+pub(crate) fn cbrt(x: f32) -> f32 {
+    libm::cbrtf(x)
+}
+
+/// Cube root.
+#[cfg(not(feature = "no-std"))]
+#[inline(always)]
+pub(crate) fn cbrt(x: f32) -> f32 {
+    x.cbrt()
+}
+
+/// Raises `x` to the power of `n`.
+#[cfg(feature = "no-std")]
+#[inline(always)]
+pub(crate) fn powf(x: f32, n: f32) -> f32 {
+    libm::powf(x, n)
+}
+
+/// Raises `x` to the power of `n`.
+#[cfg(not(feature = "no-std"))]
+#[inline(always)]
+pub(crate) fn powf(x: f32, n: f32) -> f32 {
+    x.powf(n)
+}
+
+/// Square root.
+#[cfg(feature = "no-std")]
+#[inline(always)]
+pub(crate) fn sqrt(x: f32) -> f32 {
+    libm::sqrtf(x)
+}
+
+/// Square root.
+#[cfg(not(feature = "no-std"))]
+#[inline(always)]
+pub(crate) fn sqrt(x: f32) -> f32 {
+    x.sqrt()
+}
+
+/// Four quadrant arctangent of `y / x`, in radians.
+#[cfg(feature = "no-std")]
+#[inline(always)]
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    libm::atan2f(y, x)
+}
+
+/// Four quadrant arctangent of `y / x`, in radians.
+#[cfg(not(feature = "no-std"))]
+#[inline(always)]
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    y.atan2(x)
+}
+
+/// Sine of an angle in radians.
+#[cfg(feature = "no-std")]
+#[inline(always)]
+pub(crate) fn sin(x: f32) -> f32 {
+    libm::sinf(x)
+}
+
+/// Sine of an angle in radians.
+#[cfg(not(feature = "no-std"))]
+#[inline(always)]
+pub(crate) fn sin(x: f32) -> f32 {
+    x.sin()
+}
+
+/// Cosine of an angle in radians.
+#[cfg(feature = "no-std")]
+#[inline(always)]
+pub(crate) fn cos(x: f32) -> f32 {
+    libm::cosf(x)
+}
+
+/// Cosine of an angle in radians.
+#[cfg(not(feature = "no-std"))]
+#[inline(always)]
+pub(crate) fn cos(x: f32) -> f32 {
+    x.cos()
+}
+
+/// `e` raised to the power of `x`.
+#[cfg(feature = "no-std")]
+#[inline(always)]
+pub(crate) fn exp(x: f32) -> f32 {
+    libm::expf(x)
+}
+
+/// `e` raised to the power of `x`.
+#[cfg(not(feature = "no-std"))]
+#[inline(always)]
+pub(crate) fn exp(x: f32) -> f32 {
+    x.exp()
+}
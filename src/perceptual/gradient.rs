@@ -0,0 +1,199 @@
+//! Gradients interpolated in perceptual color spaces.
+
+#[cfg(feature = "no-std")]
+use core::iter::ExactSizeIterator;
+#[cfg(not(feature = "no-std"))]
+use std::iter::ExactSizeIterator;
+
+use super::{LinearRGB, Oklab};
+use crate::color_util::gradient::rgb_gradient;
+use crate::ColorRGB;
+
+/// The color space a gradient is interpolated in.
+///
+/// Each of these produces a different path between the same two colors. Going from red to
+/// green for example, `SRGB` passes through a muddy brown, `LinearRGB` through a brighter
+/// olive, and `Oklab` through a golden yellow.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GradientSpace {
+    /// Interpolates the gamma encoded sRGB components, the same as `GradientFillRGB`.
+    SRGB,
+    /// Interpolates the physical amount of light, as if cross-fading between two lamps.
+    LinearRGB,
+    /// Interpolates in OKLab, keeping the perceived lightness and hue changing evenly.
+    Oklab,
+}
+
+/// Fills an iterable object with a gradient from the `ColorRGB` values `start` to `end`,
+/// exclusive of the `end`, interpolated in a perceptual color space.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::{prelude::*, ColorRGB};
+/// use cichlid::perceptual::GradientSpace;
+///
+/// let mut colors = [ColorRGB::Black; 10];
+/// colors.gradient_fill_oklab(ColorRGB::Red, ColorRGB::Lime);
+/// assert_eq!(colors[0], ColorRGB::Red);
+///
+/// colors.gradient_fill_in_space(ColorRGB::Black, ColorRGB::White, GradientSpace::LinearRGB);
+/// assert_eq!(colors[5], ColorRGB::new(188, 188, 188));
+/// ```
+pub trait GradientFillOklab: Sized {
+    /// Fills a gradient from two RGBs's, interpolating between the two in OKLab.
+    #[inline(always)]
+    fn gradient_fill_oklab(self, start: ColorRGB, end: ColorRGB) {
+        self.gradient_fill_in_space(start, end, GradientSpace::Oklab);
+    }
+
+    /// Fills a gradient from two RGBs's, interpolating between the two in the color `space`.
+    fn gradient_fill_in_space(self, start: ColorRGB, end: ColorRGB, space: GradientSpace);
+}
+
+/// Fills an iterable object with a gradient from the `ColorRGB` values `start` to `end`,
+/// inclusive of the `end`, interpolated in a perceptual color space.
+pub trait GradientFillOklabToInclusive: Sized {
+    /// Fills a gradient from two RGBs's, interpolating between the two in OKLab, inclusive of
+    /// the end RGB.
+    #[inline(always)]
+    fn gradient_fill_oklab_to_inclusive(self, start: ColorRGB, end: ColorRGB) {
+        self.gradient_fill_in_space_to_inclusive(start, end, GradientSpace::Oklab);
+    }
+
+    /// Fills a gradient from two RGBs's, interpolating between the two in the color `space`,
+    /// inclusive of the end RGB.
+    fn gradient_fill_in_space_to_inclusive(
+        self,
+        start: ColorRGB,
+        end: ColorRGB,
+        space: GradientSpace,
+    );
+}
+
+impl<'a, T, H: 'a> GradientFillOklab for T
+where
+    T: IntoIterator<Item = &'a mut H>,
+    T::IntoIter: ExactSizeIterator,
+    H: From<ColorRGB>,
+{
+    fn gradient_fill_in_space(self, start: ColorRGB, end: ColorRGB, space: GradientSpace) {
+        let iter = self.into_iter();
+        let length = iter.len();
+        space_gradient(iter, length, start, end, space);
+    }
+}
+
+impl<'a, T, H: 'a> GradientFillOklabToInclusive for T
+where
+    T: IntoIterator<Item = &'a mut H>,
+    T::IntoIter: ExactSizeIterator + DoubleEndedIterator,
+    H: From<ColorRGB>,
+{
+    fn gradient_fill_in_space_to_inclusive(
+        self,
+        start: ColorRGB,
+        end: ColorRGB,
+        space: GradientSpace,
+    ) {
+        let mut iter = self.into_iter();
+        if let Some(t) = iter.next_back() {
+            *t = H::from(end);
+        } else {
+            return;
+        }
+        let length = iter.len();
+        space_gradient(iter, length, start, end, space);
+    }
+}
+
+/// Linearly interpolates between each of three components.
+#[inline(always)]
+fn lerp3(start: (f32, f32, f32), end: (f32, f32, f32), t: f32) -> (f32, f32, f32) {
+    (
+        start.0 + (end.0 - start.0) * t,
+        start.1 + (end.1 - start.1) * t,
+        start.2 + (end.2 - start.2) * t,
+    )
+}
+
+/// Creates a two-color gradient from two RGB values, interpolated in the color `space`.
+///
+/// This function will fill the array inclusive of the `start` RGB and exclusive of the `end` RGB.
+/// This means that after completion, `output[output.len() - 1] will not be the end color, but
+/// rather the interpolated color before `start`.
+///
+/// # Edge Cases
+///
+/// If `output` is empty, the operation returns immediately.
+pub fn space_gradient<'a, C: 'a + From<ColorRGB>, I: IntoIterator<Item = &'a mut C>>(
+    output: I,
+    length: usize,
+    start: ColorRGB,
+    end: ColorRGB,
+    space: GradientSpace,
+) {
+    if length == 0 {
+        return;
+    }
+
+    let (start, end) = match space {
+        GradientSpace::SRGB => return rgb_gradient(output, length, start, end),
+        GradientSpace::LinearRGB => {
+            let (s, e) = (LinearRGB::from(start), LinearRGB::from(end));
+            ((s.r, s.g, s.b), (e.r, e.g, e.b))
+        }
+        GradientSpace::Oklab => {
+            let (s, e) = (Oklab::from(start), Oklab::from(end));
+            ((s.l, s.a, s.b), (e.l, e.a, e.b))
+        }
+    };
+
+    let step: f32 = 1.0 / length as f32;
+    output
+        .into_iter()
+        .take(length)
+        .enumerate()
+        .for_each(|(i, c)| {
+            let (x, y, z) = lerp3(start, end, i as f32 * step);
+            let color: ColorRGB = match space {
+                GradientSpace::LinearRGB => ColorRGB::from(LinearRGB::new(x, y, z)),
+                _ => ColorRGB::from(Oklab::new(x, y, z)),
+            };
+            *c = C::from(color);
+        });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ColorRGBA;
+
+    #[test]
+    fn spaces() {
+        let mut out = [ColorRGB::Black; 4];
+        out.gradient_fill_in_space(ColorRGB::Red, ColorRGB::Lime, GradientSpace::SRGB);
+        assert_eq!(out[2], ColorRGB::new(128, 128, 0));
+        out.gradient_fill_in_space(ColorRGB::Red, ColorRGB::Lime, GradientSpace::LinearRGB);
+        assert_eq!(out[2], ColorRGB::new(188, 188, 0));
+        out.gradient_fill_oklab(ColorRGB::Red, ColorRGB::Lime);
+        assert_eq!(out[0], ColorRGB::Red);
+        assert_eq!(out[2], ColorRGB::new(208, 168, 0));
+
+        let oklab = |c: ColorRGB| Oklab::from(c).l;
+        assert!(oklab(out[1]) > oklab(out[0]) && oklab(out[2]) > oklab(out[1]));
+    }
+
+    #[test]
+    fn inclusive_and_generic() {
+        let mut out = [ColorRGBA::TRANSPARENT; 5];
+        out.gradient_fill_oklab_to_inclusive(ColorRGB::Blue, ColorRGB::White);
+        assert_eq!(out[0], ColorRGBA::from(ColorRGB::Blue));
+        assert_eq!(out[4], ColorRGBA::from(ColorRGB::White));
+
+        let mut empty: [ColorRGB; 0] = [];
+        empty.gradient_fill_oklab_to_inclusive(ColorRGB::Blue, ColorRGB::White);
+        empty.gradient_fill_oklab(ColorRGB::Blue, ColorRGB::White);
+    }
+}
@@ -0,0 +1,432 @@
+//! Perceptual color spaces: linear-light RGB, CIE XYZ, CIE Lab and OKLab.
+//!
+//! `ColorRGB` stores gamma encoded sRGB, where equal steps in each component aren't seen as
+//! equal steps in color. The spaces in this module separate lightness from hue, so that
+//! distances and gradients between colors look even to the eye. All of them use the D65
+//! white point of sRGB.
+//!
+//! The floating point types (`LinearRGB`, `XYZ`, `Lab`, and `Oklab`) use `f32`, calling into
+//! `libm` when the `no-std` feature is enabled. For micro-controllers without a floating point
+//! unit, [`Lab16`] and [`Oklab16`] are fixed point approximations computed using only integer
+//! math.
+//!
+//! Strips can be filled with gradients interpolated in OKLab or linear light through the
+//! [`GradientFillOklab`] trait, avoiding the dull midpoints of interpolating sRGB directly.
+//!
+//! # Examples
+//!
+//! ```
+//! use cichlid::ColorRGB;
+//! use cichlid::perceptual::{Lab, Oklab};
+//!
+//! let lab = Lab::from(ColorRGB::Red);
+//! assert!((lab.l - 53.24).abs() < 0.01);
+//!
+//! let oklab = Oklab::from(ColorRGB::new(10, 200, 30));
+//! assert_eq!(ColorRGB::from(oklab), ColorRGB::new(10, 200, 30));
+//! ```
+//!
+//! [`Lab16`]: ./struct.Lab16.html
+//! [`Oklab16`]: ./struct.Oklab16.html
+//! [`GradientFillOklab`]: ./trait.GradientFillOklab.html
+
+mod difference;
+mod fixed;
+mod float;
+mod gradient;
+
+pub use self::fixed::{Lab16, Oklab16};
+pub use self::gradient::{
+    space_gradient, GradientFillOklab, GradientFillOklabToInclusive, GradientSpace,
+};
+
+use crate::ColorRGB;
+
+/// The D65 white point, as `(X, Y, Z)`.
+const WHITE: (f32, f32, f32) = (0.950_47, 1.0, 1.088_83);
+
+/// `(6/29)^3`, below which the Lab curve becomes linear.
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+
+/// `(29/3)^3`, the slope of the linear section of the Lab curve.
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+/// Converts an sRGB component to linear light, from `0.0` to `1.0`.
+#[inline]
+pub fn srgb_to_linear(c: u8) -> f32 {
+    let c: f32 = f32::from(c) / 255.0;
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        float::powf((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Converts a linear light component to sRGB, clamping to the range `0.0` to `1.0` first.
+#[inline]
+pub fn linear_to_srgb(c: f32) -> u8 {
+    let c: f32 = if c <= 0.0 {
+        return 0;
+    } else if c >= 1.0 {
+        return 255;
+    } else if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * float::powf(c, 1.0 / 2.4) - 0.055
+    };
+    (c * 255.0 + 0.5) as u8
+}
+
+/// A color in linear-light sRGB, with each component from `0.0` to `1.0`.
+///
+/// Unlike `ColorRGB`, doubling a component here doubles the amount of light emitted, which
+/// makes this the space to mix and average light in.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct LinearRGB {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl LinearRGB {
+    /// Creates a new `LinearRGB`.
+    #[inline(always)]
+    pub const fn new(r: f32, g: f32, b: f32) -> Self {
+        LinearRGB { r, g, b }
+    }
+}
+
+/// A color in the CIE 1931 XYZ space, where `Y` is the relative luminance from `0.0` to `1.0`.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct XYZ {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl XYZ {
+    /// Creates a new `XYZ`.
+    #[inline(always)]
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        XYZ { x, y, z }
+    }
+}
+
+/// A color in the CIE 1976 L\*a\*b\* space.
+///
+/// `l` is the lightness from `0.0` to `100.0`, while `a` (green to red) and `b` (blue to
+/// yellow) fall roughly between `-128.0` and `128.0`.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+impl Lab {
+    /// Creates a new `Lab`.
+    #[inline(always)]
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Lab { l, a, b }
+    }
+}
+
+/// A color in Björn Ottosson's [OKLab](https://bottosson.github.io/posts/oklab/) space.
+///
+/// `l` is the lightness from `0.0` to `1.0`, while `a` and `b` stay within about `-0.4` to
+/// `0.4` for sRGB colors. OKLab predicts lightness and hue more evenly than `Lab`, especially
+/// for blues, making it the better choice for gradients.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+impl Oklab {
+    /// Creates a new `Oklab`.
+    #[inline(always)]
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Oklab { l, a, b }
+    }
+}
+
+impl From<ColorRGB> for LinearRGB {
+    #[inline]
+    fn from(color: ColorRGB) -> Self {
+        LinearRGB::new(
+            srgb_to_linear(color.r),
+            srgb_to_linear(color.g),
+            srgb_to_linear(color.b),
+        )
+    }
+}
+
+impl From<LinearRGB> for ColorRGB {
+    /// Converts back to sRGB, clamping colors outside of the sRGB gamut.
+    #[inline]
+    fn from(color: LinearRGB) -> Self {
+        ColorRGB::new(
+            linear_to_srgb(color.r),
+            linear_to_srgb(color.g),
+            linear_to_srgb(color.b),
+        )
+    }
+}
+
+impl From<LinearRGB> for XYZ {
+    fn from(c: LinearRGB) -> Self {
+        XYZ::new(
+            0.412_456_4 * c.r + 0.357_576_1 * c.g + 0.180_437_5 * c.b,
+            0.212_672_9 * c.r + 0.715_152_2 * c.g + 0.072_175 * c.b,
+            0.019_333_9 * c.r + 0.119_192 * c.g + 0.950_304_1 * c.b,
+        )
+    }
+}
+
+impl From<XYZ> for LinearRGB {
+    fn from(c: XYZ) -> Self {
+        LinearRGB::new(
+            3.240_454_2 * c.x - 1.537_138_5 * c.y - 0.498_531_4 * c.z,
+            -0.969_266 * c.x + 1.876_010_8 * c.y + 0.041_556 * c.z,
+            0.055_643_4 * c.x - 0.204_025_9 * c.y + 1.057_225_2 * c.z,
+        )
+    }
+}
+
+impl From<XYZ> for Lab {
+    fn from(c: XYZ) -> Self {
+        let f = |t: f32| -> f32 {
+            if t > LAB_EPSILON {
+                float::cbrt(t)
+            } else {
+                (LAB_KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let fx: f32 = f(c.x / WHITE.0);
+        let fy: f32 = f(c.y / WHITE.1);
+        let fz: f32 = f(c.z / WHITE.2);
+        Lab::new(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+}
+
+impl From<Lab> for XYZ {
+    fn from(c: Lab) -> Self {
+        let finv = |f: f32| -> f32 {
+            let f3: f32 = f * f * f;
+            if f3 > LAB_EPSILON {
+                f3
+            } else {
+                (116.0 * f - 16.0) / LAB_KAPPA
+            }
+        };
+        let fy: f32 = (c.l + 16.0) / 116.0;
+        let fx: f32 = fy + c.a / 500.0;
+        let fz: f32 = fy - c.b / 200.0;
+        let y: f32 = if c.l > LAB_KAPPA * LAB_EPSILON {
+            fy * fy * fy
+        } else {
+            c.l / LAB_KAPPA
+        };
+        XYZ::new(finv(fx) * WHITE.0, y * WHITE.1, finv(fz) * WHITE.2)
+    }
+}
+
+/// Finishes an OKLab conversion from LMS cone responses.
+#[inline]
+fn oklab_from_lms(l: f32, m: f32, s: f32) -> Oklab {
+    let (l, m, s) = (float::cbrt(l), float::cbrt(m), float::cbrt(s));
+    Oklab::new(
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    )
+}
+
+impl From<LinearRGB> for Oklab {
+    fn from(c: LinearRGB) -> Self {
+        oklab_from_lms(
+            0.412_221_47 * c.r + 0.536_332_54 * c.g + 0.051_445_993 * c.b,
+            0.211_903_5 * c.r + 0.680_699_5 * c.g + 0.107_396_96 * c.b,
+            0.088_302_46 * c.r + 0.281_718_84 * c.g + 0.629_978_7 * c.b,
+        )
+    }
+}
+
+impl From<XYZ> for Oklab {
+    fn from(c: XYZ) -> Self {
+        oklab_from_lms(
+            0.818_933 * c.x + 0.361_866_74 * c.y - 0.128_859_71 * c.z,
+            0.032_984_544 * c.x + 0.929_311_9 * c.y + 0.036_145_64 * c.z,
+            0.048_200_3 * c.x + 0.264_366_27 * c.y + 0.633_851_7 * c.z,
+        )
+    }
+}
+
+impl From<Oklab> for LinearRGB {
+    fn from(c: Oklab) -> Self {
+        let l: f32 = c.l + 0.396_337_78 * c.a + 0.215_803_76 * c.b;
+        let m: f32 = c.l - 0.105_561_346 * c.a - 0.063_854_17 * c.b;
+        let s: f32 = c.l - 0.089_484_18 * c.a - 1.291_485_5 * c.b;
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+        LinearRGB::new(
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_93 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        )
+    }
+}
+
+impl From<Oklab> for XYZ {
+    #[inline]
+    fn from(c: Oklab) -> Self {
+        XYZ::from(LinearRGB::from(c))
+    }
+}
+
+impl From<ColorRGB> for XYZ {
+    #[inline]
+    fn from(color: ColorRGB) -> Self {
+        XYZ::from(LinearRGB::from(color))
+    }
+}
+
+impl From<XYZ> for ColorRGB {
+    /// Converts back to sRGB, clamping colors outside of the sRGB gamut.
+    #[inline]
+    fn from(color: XYZ) -> Self {
+        ColorRGB::from(LinearRGB::from(color))
+    }
+}
+
+impl From<ColorRGB> for Lab {
+    #[inline]
+    fn from(color: ColorRGB) -> Self {
+        Lab::from(XYZ::from(color))
+    }
+}
+
+impl From<Lab> for ColorRGB {
+    /// Converts back to sRGB, clamping colors outside of the sRGB gamut.
+    #[inline]
+    fn from(color: Lab) -> Self {
+        ColorRGB::from(XYZ::from(color))
+    }
+}
+
+impl From<ColorRGB> for Oklab {
+    #[inline]
+    fn from(color: ColorRGB) -> Self {
+        Oklab::from(LinearRGB::from(color))
+    }
+}
+
+impl From<Oklab> for ColorRGB {
+    /// Converts back to sRGB, clamping colors outside of the sRGB gamut.
+    #[inline]
+    fn from(color: Oklab) -> Self {
+        ColorRGB::from(LinearRGB::from(color))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close(actual: (f32, f32, f32), expected: (f32, f32, f32), tolerance: f32) {
+        let diffs = [
+            actual.0 - expected.0,
+            actual.1 - expected.1,
+            actual.2 - expected.2,
+        ];
+        assert!(
+            diffs.iter().all(|d| d.abs() <= tolerance),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    fn lab(color: ColorRGB) -> (f32, f32, f32) {
+        let c = Lab::from(color);
+        (c.l, c.a, c.b)
+    }
+
+    fn oklab(color: ColorRGB) -> (f32, f32, f32) {
+        let c = Oklab::from(color);
+        (c.l, c.a, c.b)
+    }
+
+    #[test]
+    fn srgb_transfer() {
+        assert_eq!(srgb_to_linear(0), 0.0);
+        assert_eq!(srgb_to_linear(255), 1.0);
+        assert!((srgb_to_linear(128) - 0.215_861).abs() < 1e-5);
+        for c in 0..=255 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(c)), c);
+        }
+        assert_eq!(linear_to_srgb(-0.5), 0);
+        assert_eq!(linear_to_srgb(1.5), 255);
+    }
+
+    #[test]
+    fn xyz_reference() {
+        let c = XYZ::from(ColorRGB::Red);
+        assert_close((c.x, c.y, c.z), (0.412_456, 0.212_673, 0.019_334), 1e-5);
+        let c = XYZ::from(ColorRGB::White);
+        assert_close((c.x, c.y, c.z), WHITE, 1e-4);
+    }
+
+    #[test]
+    fn lab_reference() {
+        // Reference values from Bruce Lindbloom's calculator, using sRGB and D65.
+        assert_close(lab(ColorRGB::White), (100.0, 0.0, 0.0), 0.01);
+        assert_close(lab(ColorRGB::Black), (0.0, 0.0, 0.0), 0.01);
+        assert_close(lab(ColorRGB::Red), (53.2408, 80.0925, 67.2032), 0.01);
+        assert_close(lab(ColorRGB::Lime), (87.7347, -86.1827, 83.1793), 0.01);
+        assert_close(lab(ColorRGB::Blue), (32.2970, 79.1875, -107.8602), 0.01);
+        assert_close(lab(ColorRGB::new(128, 128, 128)), (53.5850, 0.0, 0.0), 0.01);
+    }
+
+    #[test]
+    fn oklab_reference() {
+        // Reference values from https://bottosson.github.io/posts/oklab/
+        let xyz = [
+            ((0.950, 1.000, 1.089), (1.000, 0.000, 0.000)),
+            ((1.000, 0.000, 0.000), (0.450, 1.236, -0.019)),
+            ((0.000, 1.000, 0.000), (0.922, -0.671, 0.263)),
+            ((0.000, 0.000, 1.000), (0.153, -1.415, -0.449)),
+        ];
+        for &((x, y, z), expected) in xyz.iter() {
+            let c = Oklab::from(XYZ::new(x, y, z));
+            assert_close((c.l, c.a, c.b), expected, 0.001);
+        }
+
+        assert_close(oklab(ColorRGB::White), (1.0, 0.0, 0.0), 1e-4);
+        assert_close(
+            oklab(ColorRGB::Red),
+            (0.627_955, 0.224_863, 0.125_846),
+            1e-4,
+        );
+        assert_close(
+            oklab(ColorRGB::Lime),
+            (0.866_440, -0.233_888, 0.179_498),
+            1e-4,
+        );
+        assert_close(
+            oklab(ColorRGB::Blue),
+            (0.452_014, -0.032_457, -0.311_528),
+            1e-4,
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for code in (0..0xFF_FFFFu32).step_by(0x1357) {
+            let color = ColorRGB::from_color_code(code);
+            assert_eq!(ColorRGB::from(XYZ::from(color)), color);
+            assert_eq!(ColorRGB::from(Lab::from(color)), color);
+            assert_eq!(ColorRGB::from(Oklab::from(color)), color);
+        }
+    }
+}
@@ -0,0 +1,428 @@
+//! Maps of pixel coordinates for installations that aren't simple strips or grids.
+//!
+//! A [`PixelMap`] holds a normalized `(x, y, z)` coordinate for each pixel of a strip, in the
+//! same order as the strip. Coordinates are stored as either `u8` or `u16`, where `0` and the
+//! maximum value of the type are the edges of the installation.
+//!
+//! Spatial [`Field`]s turn each coordinate into a single byte position, which can then drive
+//! gradients, rainbows, or any other lookup. Available fields are:
+//!
+//! - A linear sweep along a direction, as if a plane was passing through the pixels.
+//! - The distance from a center point.
+//! - The angle around a center point.
+//!
+//! Maps are created from `const` arrays of [`Point`]s, allowing them to be placed in flash
+//! on `no-std` targets. With the standard library, a [`PixelMapBuf`] can also be loaded
+//! from a CSV or JSON file of coordinates in any units.
+//!
+//! # Examples
+//!
+//! ```
+//! use cichlid::{ColorRGB, GradientDirection, HSV};
+//! use cichlid::pixel_map::{Field, PixelMap, Point};
+//!
+//! // Four pixels in a diamond.
+//! static POINTS: [Point<u8>; 4] = [
+//!     Point::new(128, 0, 0),
+//!     Point::new(255, 128, 0),
+//!     Point::new(128, 255, 0),
+//!     Point::new(0, 128, 0),
+//! ];
+//! let map = PixelMap::new(&POINTS);
+//!
+//! let mut leds = [ColorRGB::Black; 4];
+//!
+//! // Sweep a gradient from top to bottom.
+//! let start = HSV::new(0, 255, 255);
+//! let end = HSV::new(160, 255, 255);
+//! map.gradient_fill(&mut leds, Field::Plane([0, 1, 0]), start, end, GradientDirection::Forward);
+//! assert_eq!(leds[0], ColorRGB::from(start));
+//! assert_eq!(leds[2], ColorRGB::from(end));
+//!
+//! // Spin a rainbow around the center.
+//! map.rainbow_fill(&mut leds, Field::Angle(Point::new(128, 128, 0)), 0);
+//! ```
+//!
+//! [`PixelMap`]: ./struct.PixelMap.html
+//! [`PixelMapBuf`]: ./struct.PixelMapBuf.html
+//! [`Field`]: ./enum.Field.html
+//! [`Point`]: ./struct.Point.html
+
+#[cfg(feature = "no-std")]
+use core::slice;
+#[cfg(not(feature = "no-std"))]
+use std::slice;
+
+use crate::math::{atan2_u16, sqrt_u64};
+use crate::{GradientDirection, HSV};
+
+#[cfg(not(feature = "no-std"))]
+mod parse;
+
+#[cfg(not(feature = "no-std"))]
+pub use self::parse::{ParseMapError, PixelMapBuf};
+
+/// Integer types usable as normalized coordinates.
+///
+/// This is implemented for `u8` and `u16`.
+pub trait Coordinate: Copy {
+    /// The largest coordinate, at the far edge of an installation.
+    const MAX: Self;
+
+    /// Converts the coordinate to a `u16`, keeping the same relative position.
+    fn to_u16(self) -> u16;
+
+    /// Converts from a `u16`, keeping the same relative position.
+    fn from_u16(value: u16) -> Self;
+}
+
+impl Coordinate for u8 {
+    const MAX: u8 = u8::MAX;
+
+    #[inline(always)]
+    fn to_u16(self) -> u16 {
+        u16::from(self) * 257
+    }
+
+    #[inline(always)]
+    fn from_u16(value: u16) -> u8 {
+        (value >> 8) as u8
+    }
+}
+
+impl Coordinate for u16 {
+    const MAX: u16 = u16::MAX;
+
+    #[inline(always)]
+    fn to_u16(self) -> u16 {
+        self
+    }
+
+    #[inline(always)]
+    fn from_u16(value: u16) -> u16 {
+        value
+    }
+}
+
+/// The normalized position of a single pixel.
+///
+/// Two dimensional installations should leave `z` at zero.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub struct Point<T = u8> {
+    /// Position along the x axis.
+    pub x: T,
+    /// Position along the y axis.
+    pub y: T,
+    /// Position along the z axis.
+    pub z: T,
+}
+
+impl<T> Point<T> {
+    /// Creates a new `Point`.
+    #[inline(always)]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point { x, y, z }
+    }
+}
+
+impl<T: Coordinate> Point<T> {
+    /// Returns each coordinate as an `i32` in the range of a `u16`.
+    #[inline(always)]
+    fn wide(self) -> [i32; 3] {
+        [
+            i32::from(self.x.to_u16()),
+            i32::from(self.y.to_u16()),
+            i32::from(self.z.to_u16()),
+        ]
+    }
+}
+
+/// A spatial function mapping each coordinate of a `PixelMap` to a position from `0` to `255`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Field<T = u8> {
+    /// A plane sweeping along the direction `[x, y, z]`.
+    ///
+    /// The pixel furthest back along the direction is at position `0`, and the pixel furthest
+    /// forward is at position `255`.
+    Plane([i16; 3]),
+    /// The distance from a center point.
+    ///
+    /// The center is at position `0`, and the pixel furthest from it is at position `255`.
+    Radial(Point<T>),
+    /// The angle around a center point, ignoring the z axis.
+    ///
+    /// Angles start at the positive x axis and increase towards the positive y axis, where
+    /// `256` is a complete turn.
+    Angle(Point<T>),
+}
+
+/// A `Field` with the normalization over a map already calculated.
+#[derive(Copy, Clone, Debug)]
+enum Prepared {
+    Plane { dir: [i64; 3], min: i64, range: i64 },
+    Radial { center: [i32; 3], max_dist: u64 },
+    Angle { center: [i32; 3] },
+}
+
+impl Prepared {
+    fn new<T: Coordinate>(points: &[Point<T>], field: Field<T>) -> Self {
+        match field {
+            Field::Plane(dir) => {
+                let dir = [i64::from(dir[0]), i64::from(dir[1]), i64::from(dir[2])];
+                let (min, max) = points
+                    .iter()
+                    .map(|p| project(dir, p.wide()))
+                    .fold((i64::MAX, i64::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+                Prepared::Plane {
+                    dir,
+                    min,
+                    range: max.saturating_sub(min),
+                }
+            }
+            Field::Radial(center) => {
+                let center = center.wide();
+                let max_dist = points
+                    .iter()
+                    .map(|p| dist_sq(center, p.wide()))
+                    .max()
+                    .unwrap_or(0);
+                Prepared::Radial { center, max_dist }
+            }
+            Field::Angle(center) => Prepared::Angle {
+                center: center.wide(),
+            },
+        }
+    }
+
+    #[inline]
+    fn position(&self, point: [i32; 3]) -> u8 {
+        match *self {
+            Prepared::Plane { dir, min, range } => match range {
+                0 => 0,
+                _ => ((((project(dir, point) - min) * 255) + range / 2) / range) as u8,
+            },
+            Prepared::Radial { center, max_dist } => match max_dist {
+                0 => 0,
+                _ => sqrt_u64(dist_sq(center, point) * 65025 / max_dist) as u8,
+            },
+            Prepared::Angle { center } => {
+                let angle = atan2_u16(point[1] - center[1], point[0] - center[0]);
+                (angle.wrapping_add(0x80) >> 8) as u8
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn project(dir: [i64; 3], point: [i32; 3]) -> i64 {
+    dir[0] * i64::from(point[0]) + dir[1] * i64::from(point[1]) + dir[2] * i64::from(point[2])
+}
+
+#[inline(always)]
+fn dist_sq(a: [i32; 3], b: [i32; 3]) -> u64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            let d = u64::from((a - b).unsigned_abs());
+            d * d
+        })
+        .sum()
+}
+
+/// Iterator over the position of each pixel in a `PixelMap` for a `Field`.
+///
+/// Created by `PixelMap::positions()`.
+#[derive(Clone, Debug)]
+pub struct Positions<'a, T> {
+    points: slice::Iter<'a, Point<T>>,
+    field: Prepared,
+}
+
+impl<'a, T: Coordinate> Iterator for Positions<'a, T> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        self.points.next().map(|p| self.field.position(p.wide()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.points.size_hint()
+    }
+}
+
+impl<'a, T: Coordinate> ExactSizeIterator for Positions<'a, T> {}
+
+/// Normalized coordinates for each pixel of a strip.
+///
+/// See the [module level documentation](./index.html) for more.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct PixelMap<'a, T = u8> {
+    points: &'a [Point<T>],
+}
+
+impl<'a, T: Coordinate> PixelMap<'a, T> {
+    /// Creates a new `PixelMap` from the coordinates of each pixel.
+    #[inline(always)]
+    pub const fn new(points: &'a [Point<T>]) -> Self {
+        PixelMap { points }
+    }
+
+    /// Returns the coordinates of each pixel.
+    #[inline(always)]
+    pub const fn points(&self) -> &'a [Point<T>] {
+        self.points
+    }
+
+    /// Returns the number of pixels in the map.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns if the map has no pixels.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns an iterator over the position of each pixel in a `Field`.
+    #[inline]
+    pub fn positions(&self, field: Field<T>) -> Positions<'a, T> {
+        Positions {
+            points: self.points.iter(),
+            field: Prepared::new(self.points, field),
+        }
+    }
+
+    /// Sets each pixel to the color returned by `f` for its position in a `Field`.
+    ///
+    /// Pixels past the end of the map are left untouched.
+    pub fn fill<'p, C: 'p, I, F>(&self, pixels: I, field: Field<T>, mut f: F)
+    where
+        I: IntoIterator<Item = &'p mut C>,
+        F: FnMut(u8) -> C,
+    {
+        pixels
+            .into_iter()
+            .zip(self.positions(field))
+            .for_each(|(p, pos)| *p = f(pos));
+    }
+
+    /// Fills a gradient from `start` to `end` (inclusive), following the position of each
+    /// pixel in a `Field`.
+    pub fn gradient_fill<'p, C: 'p + From<HSV>, I>(
+        &self,
+        pixels: I,
+        field: Field<T>,
+        start: HSV,
+        end: HSV,
+        dir: GradientDirection,
+    ) where
+        I: IntoIterator<Item = &'p mut C>,
+    {
+        let hue_dist: i32 = dir.into_hue_distance(start.h, end.h);
+        let sat_dist: i32 = i32::from(end.s) - i32::from(start.s);
+        let val_dist: i32 = i32::from(end.v) - i32::from(start.v);
+        let lerp = |from: u8, dist: i32, pos: u8| {
+            (i32::from(from) + (dist * i32::from(pos) + 127).div_euclid(255)) as u8
+        };
+        self.fill(pixels, field, |pos| {
+            C::from(HSV::new(
+                lerp(start.h, hue_dist, pos),
+                lerp(start.s, sat_dist, pos),
+                lerp(start.v, val_dist, pos),
+            ))
+        });
+    }
+
+    /// Fills a rainbow, where the hue of each pixel is `start_hue` plus its position in a
+    /// `Field`.
+    #[inline]
+    pub fn rainbow_fill<'p, C: 'p + From<HSV>, I>(&self, pixels: I, field: Field<T>, start_hue: u8)
+    where
+        I: IntoIterator<Item = &'p mut C>,
+    {
+        self.fill(pixels, field, |pos| {
+            C::from(HSV::new(start_hue.wrapping_add(pos), 255, 255))
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ColorRGB;
+
+    static LINE: [Point<u8>; 5] = [
+        Point::new(0, 0, 0),
+        Point::new(64, 0, 0),
+        Point::new(128, 0, 0),
+        Point::new(192, 0, 0),
+        Point::new(255, 0, 0),
+    ];
+
+    static RING: [Point<u16>; 4] = [
+        Point::new(65535, 32768, 0),
+        Point::new(32768, 65535, 0),
+        Point::new(0, 32768, 0),
+        Point::new(32768, 0, 0),
+    ];
+
+    #[test]
+    fn plane_sweep() {
+        let map = PixelMap::new(&LINE);
+        let mut out = [0u8; 5];
+        map.fill(out.iter_mut(), Field::Plane([1, 0, 0]), |p| p);
+        assert_eq!(out, [0, 64, 128, 192, 255]);
+        map.fill(out.iter_mut(), Field::Plane([-3, 0, 0]), |p| p);
+        assert_eq!(out, [255, 191, 127, 63, 0]);
+        map.fill(out.iter_mut(), Field::Plane([0, 1, 1]), |p| p);
+        assert_eq!(out, [0; 5]);
+    }
+
+    #[test]
+    fn radial() {
+        let map = PixelMap::new(&LINE);
+        let mut out = [0u8; 5];
+        map.fill(out.iter_mut(), Field::Radial(Point::new(128, 0, 0)), |p| p);
+        assert_eq!(out, [255, 127, 0, 127, 253]);
+
+        let map = PixelMap::new(&RING);
+        let center = Point::new(32768, 32768, 0);
+        assert!(map.positions(Field::Radial(center)).all(|p| p >= 254));
+    }
+
+    #[test]
+    fn angle() {
+        let map = PixelMap::new(&RING);
+        let center = Point::new(32768, 32768, 0);
+        let mut out = [0u8; 4];
+        map.fill(out.iter_mut(), Field::Angle(center), |p| p);
+        assert_eq!(out, [0, 64, 128, 192]);
+    }
+
+    #[test]
+    fn gradients() {
+        let map = PixelMap::new(&LINE);
+        let mut leds = [ColorRGB::Black; 6];
+        let start = HSV::new(250, 255, 100);
+        let end = HSV::new(10, 155, 200);
+        map.gradient_fill(
+            &mut leds,
+            Field::Plane([1, 0, 0]),
+            start,
+            end,
+            GradientDirection::Shortest,
+        );
+        assert_eq!(leds[0], ColorRGB::from(start));
+        assert_eq!(leds[2], ColorRGB::from(HSV::new(2, 205, 150)));
+        assert_eq!(leds[4], ColorRGB::from(end));
+        assert_eq!(leds[5], ColorRGB::Black);
+
+        let mut hues = [HSV::BLANK; 5];
+        map.rainbow_fill(&mut hues, Field::Plane([1, 0, 0]), 100);
+        assert_eq!(hues[1], HSV::new(164, 255, 255));
+    }
+}
@@ -0,0 +1,391 @@
+//! Loading pixel maps from CSV and JSON files.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::{Coordinate, PixelMap, Point};
+
+/// Error returned when a CSV or JSON pixel map cannot be parsed.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParseMapError {
+    line: usize,
+    kind: ErrorKind,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ErrorKind {
+    InvalidNumber,
+    WrongDimensions,
+    UnexpectedCharacter,
+    UnexpectedEnd,
+}
+
+impl ParseMapError {
+    /// Returns the line of the file (starting at one) where the error occurred.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let desc = match self.kind {
+            ErrorKind::InvalidNumber => "invalid number",
+            ErrorKind::WrongDimensions => "expected two or three coordinates",
+            ErrorKind::UnexpectedCharacter => "unexpected character",
+            ErrorKind::UnexpectedEnd => "unexpected end of file",
+        };
+        write!(f, "{} on line {}", desc, self.line)
+    }
+}
+
+impl Error for ParseMapError {}
+
+/// An owned `PixelMap`, loaded from a file of coordinates.
+///
+/// Coordinates in the file can be in any units. When loaded, they are shifted so the smallest
+/// coordinate on each axis is zero, then scaled uniformly so the largest extent of the
+/// installation spans the full range of `T`. This keeps the proportions of the installation.
+///
+/// # File Formats
+///
+/// CSV files have one pixel per line, each with two (`x, y`) or three (`x, y, z`) comma
+/// separated coordinates. Empty lines, lines starting with `#`, and a header on the first
+/// line are skipped.
+///
+/// JSON files contain a single array, with each pixel either an array of two or three
+/// numbers, or an object with the keys `"x"`, `"y"`, and optionally `"z"`.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::pixel_map::{PixelMapBuf, Point};
+///
+/// let csv = "x,y\n0.0, 0.0\n2.5, 1.25\n5, 0";
+/// let map: PixelMapBuf<u8> = PixelMapBuf::from_csv(csv).unwrap();
+/// assert_eq!(map.points()[1], Point::new(128, 64, 0));
+///
+/// let json = r#"[{"x": 0, "y": 10}, [5, 10, 2.5]]"#;
+/// let map: PixelMapBuf<u16> = PixelMapBuf::from_json(json).unwrap();
+/// assert_eq!(map.points()[1], Point::new(65535, 0, 32768));
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct PixelMapBuf<T = u8> {
+    points: Vec<Point<T>>,
+}
+
+impl<T: Coordinate> PixelMapBuf<T> {
+    /// Parses a map from CSV text.
+    pub fn from_csv(text: &str) -> Result<Self, ParseMapError> {
+        let mut raw: Vec<[f64; 3]> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut coords = [0.0; 3];
+            let mut count: usize = 0;
+            let mut valid = true;
+            for value in line.split(',') {
+                match value.trim().parse::<f64>() {
+                    Ok(v) if v.is_finite() => {
+                        if count < 3 {
+                            coords[count] = v;
+                        }
+                        count += 1;
+                    }
+                    _ => valid = false,
+                }
+            }
+            let error = |kind| ParseMapError { line: i + 1, kind };
+            if !valid {
+                // Allow for a header.
+                if raw.is_empty() && i == first_content_line(text) {
+                    continue;
+                }
+                return Err(error(ErrorKind::InvalidNumber));
+            }
+            if count != 2 && count != 3 {
+                return Err(error(ErrorKind::WrongDimensions));
+            }
+            raw.push(coords);
+        }
+        Ok(Self::normalize(&raw))
+    }
+
+    /// Parses a map from JSON text.
+    pub fn from_json(text: &str) -> Result<Self, ParseMapError> {
+        let mut parser = JsonParser { text, pos: 0 };
+        let raw = parser.parse()?;
+        Ok(Self::normalize(&raw))
+    }
+
+    /// Reads and parses a map from a CSV file.
+    ///
+    /// Parsing errors are returned as an `io::Error` of the kind `InvalidData`.
+    pub fn read_csv<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_csv(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Reads and parses a map from a JSON file.
+    ///
+    /// Parsing errors are returned as an `io::Error` of the kind `InvalidData`.
+    pub fn read_json<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_json(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the coordinates of each pixel.
+    #[inline]
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    /// Borrows the map as a `PixelMap`.
+    #[inline]
+    pub fn as_pixel_map(&self) -> PixelMap<'_, T> {
+        PixelMap::new(&self.points)
+    }
+
+    fn normalize(raw: &[[f64; 3]]) -> Self {
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+        for coords in raw.iter() {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(coords[axis]);
+                max[axis] = max[axis].max(coords[axis]);
+            }
+        }
+        let extent = (0..3).map(|a| max[a] - min[a]).fold(0.0, f64::max);
+        let scale = match extent > 0.0 {
+            true => f64::from(T::MAX.to_u16()) / extent,
+            false => 0.0,
+        };
+        let convert = |v: f64, axis: usize| T::from_u16(((v - min[axis]) * scale).round() as u16);
+        let points = raw
+            .iter()
+            .map(|c| Point::new(convert(c[0], 0), convert(c[1], 1), convert(c[2], 2)))
+            .collect();
+        PixelMapBuf { points }
+    }
+}
+
+/// Returns the index of the first line with content.
+fn first_content_line(text: &str) -> usize {
+    text.lines()
+        .position(|l| !l.trim().is_empty() && !l.trim().starts_with('#'))
+        .unwrap_or(0)
+}
+
+/// A minimal parser for JSON arrays of coordinates.
+struct JsonParser<'t> {
+    text: &'t str,
+    pos: usize,
+}
+
+impl<'t> JsonParser<'t> {
+    fn error(&self, kind: ErrorKind) -> ParseMapError {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        ParseMapError { line, kind }
+    }
+
+    fn peek(&mut self) -> Result<u8, ParseMapError> {
+        let rest = &self.text.as_bytes()[self.pos..];
+        match rest.iter().position(|c| !c.is_ascii_whitespace()) {
+            Some(skip) => {
+                self.pos += skip;
+                Ok(rest[skip])
+            }
+            None => {
+                self.pos = self.text.len();
+                Err(self.error(ErrorKind::UnexpectedEnd))
+            }
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ParseMapError> {
+        match self.peek()? == c {
+            true => {
+                self.pos += 1;
+                Ok(())
+            }
+            false => Err(self.error(ErrorKind::UnexpectedCharacter)),
+        }
+    }
+
+    /// Parses a comma separated list until `close`, calling `item` for each element.
+    fn list<F>(&mut self, close: u8, mut item: F) -> Result<(), ParseMapError>
+    where
+        F: FnMut(&mut Self) -> Result<(), ParseMapError>,
+    {
+        if self.peek()? == close {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            match self.peek()? {
+                b',' => self.pos += 1,
+                c if c == close => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error(ErrorKind::UnexpectedCharacter)),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, ParseMapError> {
+        self.peek()?;
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or(rest.len());
+        match rest[..len].parse::<f64>() {
+            Ok(v) if v.is_finite() => {
+                self.pos += len;
+                Ok(v)
+            }
+            _ => Err(self.error(ErrorKind::InvalidNumber)),
+        }
+    }
+
+    fn string(&mut self) -> Result<&'t str, ParseMapError> {
+        self.expect(b'"')?;
+        let rest = &self.text[self.pos..];
+        match rest.find('"') {
+            Some(end) => {
+                self.pos += end + 1;
+                Ok(&rest[..end])
+            }
+            None => Err(self.error(ErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn point(&mut self) -> Result<[f64; 3], ParseMapError> {
+        let mut coords = [0.0; 3];
+        let first: u8 = self.peek()?;
+        let start = self.pos;
+        let valid: bool = match first {
+            b'[' => {
+                self.pos += 1;
+                let mut count: usize = 0;
+                self.list(b']', |p| {
+                    let v = p.number()?;
+                    if count < 3 {
+                        coords[count] = v;
+                    }
+                    count += 1;
+                    Ok(())
+                })?;
+                count == 2 || count == 3
+            }
+            b'{' => {
+                self.pos += 1;
+                let mut found = [false; 3];
+                self.list(b'}', |p| {
+                    let key = p.string()?;
+                    p.expect(b':')?;
+                    let v = p.number()?;
+                    if let Some(axis) = ["x", "y", "z"].iter().position(|k| *k == key) {
+                        coords[axis] = v;
+                        found[axis] = true;
+                    }
+                    Ok(())
+                })?;
+                found[0] && found[1]
+            }
+            _ => return Err(self.error(ErrorKind::UnexpectedCharacter)),
+        };
+        match valid {
+            true => Ok(coords),
+            false => {
+                self.pos = start;
+                Err(self.error(ErrorKind::WrongDimensions))
+            }
+        }
+    }
+
+    fn parse(&mut self) -> Result<Vec<[f64; 3]>, ParseMapError> {
+        let mut raw: Vec<[f64; 3]> = Vec::new();
+        self.expect(b'[')?;
+        self.list(b']', |p| {
+            raw.push(p.point()?);
+            Ok(())
+        })?;
+        match self.peek() {
+            Err(_) => Ok(raw),
+            Ok(_) => Err(self.error(ErrorKind::UnexpectedCharacter)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn csv() {
+        let text = "# pixel positions\n\nx, y, z\n0,0,0\n10, 5, 0\n\n 10 , 10 , 10\n";
+        let map: PixelMapBuf<u8> = PixelMapBuf::from_csv(text).unwrap();
+        assert_eq!(
+            map.points(),
+            &[
+                Point::new(0, 0, 0),
+                Point::new(255, 128, 0),
+                Point::new(255, 255, 255)
+            ]
+        );
+
+        let err = PixelMapBuf::<u8>::from_csv("1,2\n3,4,5,6").unwrap_err();
+        assert_eq!(err.line(), 2);
+        let err = PixelMapBuf::<u8>::from_csv("1,2\n3,x").unwrap_err();
+        assert_eq!(err.to_string(), "invalid number on line 2");
+        assert!(PixelMapBuf::<u8>::from_csv("").unwrap().points().is_empty());
+    }
+
+    #[test]
+    fn json() {
+        let text = "[\n  [0, 0],\n  {\"y\": 4, \"x\": -4, \"id\": 7},\n  [-2, 2, 1e0]\n]";
+        let map: PixelMapBuf<u16> = PixelMapBuf::from_json(text).unwrap();
+        assert_eq!(
+            map.points(),
+            &[
+                Point::new(65535, 0, 0),
+                Point::new(0, 65535, 0),
+                Point::new(32768, 32768, 16384)
+            ]
+        );
+        assert!(PixelMapBuf::<u8>::from_json(" [ ] ")
+            .unwrap()
+            .points()
+            .is_empty());
+
+        let err = PixelMapBuf::<u8>::from_json("[\n[1, 2],\n[3]\n]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected two or three coordinates on line 3"
+        );
+        let err = PixelMapBuf::<u8>::from_json("[[1, 2]\n[3, 4]]").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected character on line 2");
+        let err = PixelMapBuf::<u8>::from_json("[[1, 2],").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected end of file on line 1");
+    }
+
+    #[test]
+    fn read_files() {
+        let path = std::env::temp_dir().join("cichlid_pixel_map_test.json");
+        fs::write(&path, "[[0, 0], [1, 1]]").unwrap();
+        let map = PixelMapBuf::<u8>::read_json(&path).unwrap();
+        assert_eq!(map.as_pixel_map().len(), 2);
+
+        fs::write(&path, "[[0, 0], [1, 1]").unwrap();
+        let err = PixelMapBuf::<u8>::read_json(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,359 @@
+//! Polar coordinates for LED rings and discs made of concentric rings.
+//!
+//! A [`RingLayout`] describes a fixture as the number of pixels in each ring, in the order the
+//! rings are wired. Pixels can then be addressed by an angle and a radius, both as a `u8`:
+//!
+//! - An angle of `0` is the first pixel of each ring, with `256` being a complete turn in the
+//!   direction the ring is wired.
+//! - A radius of `0` is the innermost ring, and a radius of `255` is the outermost ring.
+//!
+//! # Examples
+//!
+//! ```
+//! use cichlid::{prelude::*, ColorRGB, GradientDirection, HSV};
+//! use cichlid::polar::{RingLayout, RingOrder};
+//!
+//! // A common 61 pixel disc, wired from the outside in.
+//! static RINGS: [u16; 5] = [24, 16, 12, 8, 1];
+//! let layout = RingLayout::new(&RINGS, RingOrder::OuterFirst).unwrap();
+//! assert_eq!(layout.len(), 61);
+//!
+//! assert_eq!(layout.index(0, 255), 0); // First pixel of the outer ring.
+//! assert_eq!(layout.index(128, 191), 24 + 8); // Halfway around the second ring.
+//! assert_eq!(layout.polar(60), Some((0, 0))); // The center pixel.
+//!
+//! let mut leds = [ColorRGB::Black; 61];
+//! let center = HSV::new(0, 255, 255);
+//! let edge = HSV::new(160, 255, 255);
+//! layout.radial_gradient_fill(&mut leds, center, edge, GradientDirection::Forward);
+//! assert_eq!(leds[60], ColorRGB::from(center));
+//! assert_eq!(leds[0], ColorRGB::from(edge));
+//!
+//! layout.angular_rainbow_fill(&mut leds, 0);
+//! ```
+//!
+//! [`RingLayout`]: ./struct.RingLayout.html
+
+#[cfg(feature = "no-std")]
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+#[cfg(not(feature = "no-std"))]
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+
+#[cfg(feature = "no-std")]
+use core::{mem, slice};
+#[cfg(not(feature = "no-std"))]
+use std::{mem, slice};
+
+use crate::color_util::{GradientFillToInclusive, RainbowFillSingleCycle};
+use crate::{GradientDirection, HSV};
+
+/// The order rings are wired in a `RingLayout`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum RingOrder {
+    /// The innermost ring comes first in the strip.
+    InnerFirst,
+    /// The outermost ring comes first in the strip.
+    OuterFirst,
+}
+
+/// The layout of a fixture made of concentric rings.
+///
+/// See the [module level documentation](./index.html) for more.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct RingLayout<'a> {
+    counts: &'a [u16],
+    order: RingOrder,
+    len: usize,
+}
+
+impl<'a> RingLayout<'a> {
+    /// Creates a new `RingLayout` from the number of pixels in each ring, in the order they
+    /// are wired.
+    ///
+    /// Returns `None` if there are no rings, or if any ring is empty.
+    pub const fn new(counts: &'a [u16], order: RingOrder) -> Option<Self> {
+        if counts.is_empty() {
+            return None;
+        }
+        let mut len: usize = 0;
+        let mut i: usize = 0;
+        while i < counts.len() {
+            if counts[i] == 0 {
+                return None;
+            }
+            len += counts[i] as usize;
+            i += 1;
+        }
+        Some(RingLayout { counts, order, len })
+    }
+
+    /// Returns the number of pixels in each ring, in the order they are wired.
+    #[inline(always)]
+    pub const fn counts(&self) -> &'a [u16] {
+        self.counts
+    }
+
+    /// Returns the order the rings are wired in.
+    #[inline(always)]
+    pub const fn order(&self) -> RingOrder {
+        self.order
+    }
+
+    /// Returns the total number of pixels over every ring.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the layout has no pixels.
+    ///
+    /// This is never the case for a layout created by `RingLayout::new()`.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of rings.
+    #[inline(always)]
+    pub const fn rings(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Converts between the position of a ring in the strip and its position from the center.
+    #[inline(always)]
+    fn flip(&self, ring: usize) -> usize {
+        match self.order {
+            RingOrder::InnerFirst => ring,
+            RingOrder::OuterFirst => self.rings() - 1 - ring,
+        }
+    }
+
+    /// Returns the index of the pixel nearest to an `angle` and `radius`.
+    pub fn index(&self, angle: u8, radius: u8) -> usize {
+        let last: usize = self.rings() - 1;
+        let ring: usize = self.flip((usize::from(radius) * last + 127) / 255);
+        let start: usize = self.counts[..ring].iter().map(|c| usize::from(*c)).sum();
+        let count: usize = usize::from(self.counts[ring]);
+        start + ((usize::from(angle) * count + 128) >> 8) % count
+    }
+
+    /// Returns the `(angle, radius)` of the pixel at `index`, or `None` if the index is past
+    /// the last ring.
+    ///
+    /// For rings of up to 256 pixels, and up to 256 rings, `index()` exactly reverses this.
+    pub fn polar(&self, index: usize) -> Option<(u8, u8)> {
+        let last: usize = self.rings() - 1;
+        let mut start: usize = 0;
+        for (ring, count) in self.counts.iter().map(|c| usize::from(*c)).enumerate() {
+            if index < start + count {
+                let angle = (((index - start) << 8) + count / 2) / count;
+                let radius = match last {
+                    0 => 0,
+                    _ => (self.flip(ring) * 255 + last / 2) / last,
+                };
+                return Some((angle as u8, radius as u8));
+            }
+            start += count;
+        }
+        None
+    }
+
+    /// Returns an iterator over each ring of `pixels`, from the innermost ring to the
+    /// outermost ring.
+    ///
+    /// If `pixels` is shorter than the layout, the rings past its end are shortened or empty.
+    #[inline]
+    pub fn rings_mut<'p, C>(&self, pixels: &'p mut [C]) -> RingsMut<'a, 'p, C> {
+        RingsMut {
+            counts: self.counts.iter(),
+            rest: pixels,
+            reversed: self.order == RingOrder::OuterFirst,
+        }
+    }
+
+    /// Fills each ring with a single color, as a gradient from the `center` color to the
+    /// `edge` color (inclusive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` is shorter than the layout.
+    pub fn radial_gradient_fill<C>(
+        &self,
+        pixels: &mut [C],
+        center: HSV,
+        edge: HSV,
+        dir: GradientDirection,
+    ) where
+        C: From<HSV> + Copy,
+    {
+        assert!(
+            pixels.len() >= self.len,
+            "pixels are shorter than the layout"
+        );
+        self.rings_mut(pixels)
+            .map(|ring| &mut ring[0])
+            .gradient_fill_to_inclusive(center, edge, dir);
+        self.rings_mut(pixels).for_each(|ring| {
+            let color: C = ring[0];
+            ring.iter_mut().for_each(|p| *p = color);
+        });
+    }
+
+    /// Fills each ring with a complete rainbow, starting at `start_hue` for the first pixel
+    /// of each ring.
+    pub fn angular_rainbow_fill<C: From<HSV>>(&self, pixels: &mut [C], start_hue: u8) {
+        self.rings_mut(pixels)
+            .for_each(|ring| ring.rainbow_fill_single_cycle(start_hue));
+    }
+}
+
+/// Iterator over the rings of a strip, from the innermost ring to the outermost ring.
+///
+/// Created by `RingLayout::rings_mut()`.
+#[derive(Debug)]
+pub struct RingsMut<'a, 'p, C> {
+    counts: slice::Iter<'a, u16>,
+    rest: &'p mut [C],
+    reversed: bool,
+}
+
+impl<'a, 'p, C> RingsMut<'a, 'p, C> {
+    /// Takes the first ring remaining in the strip.
+    fn take_front(&mut self) -> Option<&'p mut [C]> {
+        let count: usize = usize::from(*self.counts.next()?);
+        let rest: &'p mut [C] = mem::take(&mut self.rest);
+        let (ring, rest) = rest.split_at_mut(count.min(rest.len()));
+        self.rest = rest;
+        Some(ring)
+    }
+
+    /// Takes the last ring remaining in the strip.
+    fn take_back(&mut self) -> Option<&'p mut [C]> {
+        let count: usize = usize::from(*self.counts.next_back()?);
+        let remaining: usize = self.counts.as_slice().iter().map(|c| usize::from(*c)).sum();
+        let rest: &'p mut [C] = mem::take(&mut self.rest);
+        let split: usize = remaining.min(rest.len());
+        let (rest, ring) = rest.split_at_mut(split);
+        let ring_len: usize = count.min(ring.len());
+        self.rest = rest;
+        Some(&mut ring[..ring_len])
+    }
+}
+
+impl<'a, 'p, C> Iterator for RingsMut<'a, 'p, C> {
+    type Item = &'p mut [C];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'p mut [C]> {
+        match self.reversed {
+            true => self.take_back(),
+            false => self.take_front(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.counts.size_hint()
+    }
+}
+
+impl<'a, 'p, C> DoubleEndedIterator for RingsMut<'a, 'p, C> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'p mut [C]> {
+        match self.reversed {
+            true => self.take_front(),
+            false => self.take_back(),
+        }
+    }
+}
+
+impl<'a, 'p, C> ExactSizeIterator for RingsMut<'a, 'p, C> {}
+
+impl<'a, 'p, C> FusedIterator for RingsMut<'a, 'p, C> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ColorRGB;
+
+    static RINGS: [u16; 4] = [1, 6, 12, 24];
+
+    #[test]
+    fn invalid_layouts() {
+        assert!(RingLayout::new(&[], RingOrder::InnerFirst).is_none());
+        assert!(RingLayout::new(&[3, 0, 1], RingOrder::InnerFirst).is_none());
+    }
+
+    #[test]
+    fn index_round_trip() {
+        static BIG: [u16; 3] = [256, 100, 7];
+        for &counts in [&RINGS[..], &BIG[..], &[5][..]].iter() {
+            for &order in [RingOrder::InnerFirst, RingOrder::OuterFirst].iter() {
+                let layout = RingLayout::new(counts, order).unwrap();
+                for i in 0..layout.len() {
+                    let (angle, radius) = layout.polar(i).unwrap();
+                    assert_eq!(layout.index(angle, radius), i, "{:?} {:?}", counts, order);
+                }
+                assert_eq!(layout.polar(layout.len()), None);
+            }
+        }
+    }
+
+    #[test]
+    fn index_positions() {
+        let layout = RingLayout::new(&RINGS, RingOrder::InnerFirst).unwrap();
+        assert_eq!(layout.index(200, 0), 0);
+        assert_eq!(layout.index(0, 85), 1);
+        assert_eq!(layout.index(64, 170), 7 + 3);
+        assert_eq!(layout.index(255, 255), 19);
+        assert_eq!(layout.index(250, 255), 19 + 23);
+        assert_eq!(layout.polar(7 + 6), Some((128, 170)));
+    }
+
+    #[test]
+    fn rings_iterate_from_center() {
+        let mut leds = [0u8; 43];
+        let layout = RingLayout::new(&RINGS, RingOrder::OuterFirst).unwrap();
+        let lens: [usize; 4] = {
+            let mut lens = [0; 4];
+            lens.iter_mut()
+                .zip(layout.rings_mut(&mut leds))
+                .for_each(|(l, r)| *l = r.len());
+            lens
+        };
+        assert_eq!(lens, [24, 12, 6, 1]);
+        layout
+            .rings_mut(&mut leds)
+            .enumerate()
+            .for_each(|(i, r)| r.iter_mut().for_each(|p| *p = i as u8));
+        assert_eq!(leds[0], 3);
+        assert_eq!(leds[42], 0);
+
+        // Rings past the end of the pixels are shortened.
+        let mut rings = layout.rings_mut(&mut leds[..30]);
+        assert_eq!(rings.len(), 4);
+        assert_eq!(rings.next_back().map(|r| r.len()), Some(1));
+        assert_eq!(rings.next_back().map(|r| r.len()), Some(6));
+        assert_eq!(rings.next().map(|r| r.len()), Some(11));
+        assert_eq!(rings.next().map(|r| r.len()), Some(12));
+        assert!(rings.next().is_none());
+    }
+
+    #[test]
+    fn fills() {
+        let layout = RingLayout::new(&RINGS, RingOrder::InnerFirst).unwrap();
+        let mut leds = [ColorRGB::Black; 43];
+        let center = HSV::new(0, 255, 255);
+        let edge = HSV::new(96, 255, 255);
+        layout.radial_gradient_fill(&mut leds, center, edge, GradientDirection::Forward);
+        assert_eq!(leds[0], ColorRGB::from(center));
+        assert!(leds[1..7]
+            .iter()
+            .all(|c| *c == ColorRGB::from(HSV::new(32, 255, 255))));
+        assert!(leds[19..].iter().all(|c| *c == ColorRGB::from(edge)));
+
+        layout.angular_rainbow_fill(&mut leds, 10);
+        assert_eq!(leds[0], ColorRGB::from(HSV::new(10, 255, 255)));
+        assert_eq!(leds[7 + 6], ColorRGB::from(HSV::new(137, 255, 255)));
+    }
+}
@@ -30,7 +30,7 @@ use std::ops::{
 
 use crate::color_codes::*;
 use crate::math::*;
-use crate::HSV;
+use crate::{BlendMode, HSV, HSV16};
 
 /// Object representing a color through the standard single byte red, green, and blue values.
 #[repr(packed)]
@@ -161,6 +161,226 @@ impl ColorRGB {
             self.b = self.b.blend(other.b, amount_of_other);
         }
     }
+
+    /// Combines `top` on top of this color with a `BlendMode`, blended in by `opacity`.
+    ///
+    /// See `BlendMode` for the formula used by each mode.
+    #[inline]
+    pub fn blend_with(&mut self, top: ColorRGB, mode: BlendMode, opacity: u8) {
+        *self = mode.blend(*self, top, opacity);
+    }
+
+    /// Sets every component to the luminosity of the pixel.
+    #[inline]
+    pub fn grayscale(&mut self) {
+        let luma: u8 = self.luma();
+        *self = ColorRGB::new(luma, luma, luma);
+    }
+
+    /// Desaturates the color towards a gray of the same luminosity.
+    ///
+    /// The parameter `amount` is read as a fractional component, an `amount` of `255`
+    /// results in the same color as `grayscale()`, while an `amount` of `0` leaves the
+    /// color unchanged.
+    #[inline]
+    pub fn desaturate(&mut self, amount: u8) {
+        let luma: u8 = self.luma();
+        self.blend(ColorRGB::new(luma, luma, luma), amount);
+    }
+
+    /// Saturates the color away from a gray of the same luminosity.
+    ///
+    /// The parameter `amount` is read as a fractional component, where an `amount` of `255`
+    /// nearly doubles the distance of each component from the luminosity. The increase is
+    /// limited so that no component is clipped, which preserves the hue of the color.
+    pub fn saturate(&mut self, amount: u8) {
+        let luma: i32 = i32::from(self.luma());
+        // 8.8 fixed point multiplier for the distance from the luminosity.
+        let mut factor: i32 = 256 + i32::from(amount);
+        for &c in [self.r, self.g, self.b].iter() {
+            let dist: i32 = i32::from(c) - luma;
+            if dist > 0 {
+                factor = factor.min(((255 - luma) << 8) / dist);
+            } else if dist < 0 {
+                factor = factor.min((luma << 8) / -dist);
+            }
+        }
+        self.modify_all(|c| (luma + (((i32::from(c) - luma) * factor) >> 8)) as u8);
+    }
+
+    /// Rotates the hue of the color by `amount`, where `256` is a complete rotation.
+    ///
+    /// The rotation is done around a mathematical (spectrum) color wheel, with red, green,
+    /// and blue evenly spaced. The largest and smallest components are unchanged, so the
+    /// saturation and value of the color are preserved.
+    pub fn hue_rotate(&mut self, amount: u8) {
+        let pos: u32 = match self.spectrum_position() {
+            Some(pos) if amount != 0 => (pos + 6 * u32::from(amount)) % (6 * 256),
+            _ => return,
+        };
+        let max: u8 = self.r.max(self.g).max(self.b);
+        let min: u8 = self.r.min(self.g).min(self.b);
+        let chroma: u32 = u32::from(max - min);
+
+        let x: u8 = ((chroma * (pos % 256) + 128) >> 8) as u8;
+        *self = match pos / 256 {
+            0 => mk_rgb!(max, min + x, min),
+            1 => mk_rgb!(max - x, max, min),
+            2 => mk_rgb!(min, max, min + x),
+            3 => mk_rgb!(min, max - x, max),
+            4 => mk_rgb!(min + x, min, max),
+            _ => mk_rgb!(max, min, max - x),
+        };
+    }
+
+    /// Returns the position of the hue around a mathematical (spectrum) color wheel, where
+    /// each of the six sections between a primary and secondary color is `256` steps.
+    ///
+    /// Shades of gray have no hue, and return `None`.
+    pub(crate) fn spectrum_position(self) -> Option<u32> {
+        let (r, g, b) = (u32::from(self.r), u32::from(self.g), u32::from(self.b));
+        let max: u32 = r.max(g).max(b);
+        let chroma: u32 = max - r.min(g).min(b);
+        if chroma == 0 {
+            return None;
+        }
+        let num: u32 = if r == max {
+            (6 * 256 * chroma + 256 * g - 256 * b) % (6 * 256 * chroma)
+        } else if g == max {
+            2 * 256 * chroma + 256 * b - 256 * r
+        } else {
+            4 * 256 * chroma + 256 * r - 256 * g
+        };
+        Some((num + chroma / 2) / chroma)
+    }
+
+    /// Rotates the hue of the color halfway around the color wheel.
+    ///
+    /// This is the same as `hue_rotate(128)`, but exact.
+    #[inline]
+    pub fn invert_hue(&mut self) {
+        let max: u8 = self.r.max(self.g).max(self.b);
+        let min: u8 = self.r.min(self.g).min(self.b);
+        self.modify_all(|c| max - c + min);
+    }
+
+    /// Looks up a color from `color_codes` by its name, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    ///
+    /// assert_eq!(ColorRGB::from_name("darkOrange"), Some(ColorRGB::DarkOrange));
+    /// assert_eq!(ColorRGB::from_name("Not A Color"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<ColorRGB> {
+        NAMED_COLORS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, color)| *color)
+    }
+
+    /// Returns the name of the color, if it exactly matches one from `color_codes`.
+    ///
+    /// Some colors have more than one name, such as `Aqua` and `Cyan`. In this case, the
+    /// name that comes first alphabetically is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    ///
+    /// assert_eq!(ColorRGB::new(255, 140, 0).name(), Some("DarkOrange"));
+    /// assert_eq!(ColorRGB::Cyan.name(), Some("Aqua"));
+    /// assert_eq!(ColorRGB::new(255, 140, 1).name(), None);
+    /// ```
+    pub fn name(self) -> Option<&'static str> {
+        NAMED_COLORS
+            .iter()
+            .find(|(_, color)| *color == self)
+            .map(|(n, _)| *n)
+    }
+
+    /// Returns the name and value of the color from `color_codes` that looks the most like
+    /// this color.
+    ///
+    /// Colors are compared using `distance_sq()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    ///
+    /// assert_eq!(ColorRGB::new(250, 130, 10).nearest_named(), ("DarkOrange", ColorRGB::DarkOrange));
+    /// assert_eq!(ColorRGB::Gold.nearest_named().0, "Gold");
+    /// ```
+    pub fn nearest_named(self) -> (&'static str, ColorRGB) {
+        let mut nearest: (&'static str, ColorRGB) = NAMED_COLORS[0];
+        let mut nearest_dist: u32 = u32::MAX;
+        for &(name, color) in NAMED_COLORS.iter() {
+            let dist: u32 = self.distance_sq(color);
+            if dist < nearest_dist {
+                nearest = (name, color);
+                nearest_dist = dist;
+            }
+        }
+        nearest
+    }
+
+    /// Returns the index of the color in `palette` that looks the most like this color, or
+    /// `None` if `palette` is empty.
+    ///
+    /// Colors are compared using `distance_sq()`. If more than one color is equally close,
+    /// the first of them is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    ///
+    /// let palette = [ColorRGB::Red, ColorRGB::Lime, ColorRGB::Blue];
+    /// assert_eq!(ColorRGB::new(30, 200, 60).nearest_in_palette(&palette), Some(1));
+    /// assert_eq!(ColorRGB::Red.nearest_in_palette(&[]), None);
+    /// ```
+    pub fn nearest_in_palette(self, palette: &[ColorRGB]) -> Option<usize> {
+        palette
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, color)| self.distance_sq(**color))
+            .map(|(i, _)| i)
+    }
+
+    /// Returns the squared distance to another color, weighted by how sensitive the eye is to
+    /// each component.
+    ///
+    /// This is the "redmean" approximation, where the weights of red and blue depend on how
+    /// red the two colors are. It's a far better match for perceived differences than the
+    /// plain euclidean distance, while needing only integer math. Identical colors have a
+    /// distance of `0`, and the furthest apart (black and white) have a distance of `584_970`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::ColorRGB;
+    ///
+    /// assert_eq!(ColorRGB::Black.distance_sq(ColorRGB::White), 584_970);
+    /// assert_eq!(ColorRGB::Red.distance_sq(ColorRGB::Red), 0);
+    ///
+    /// // Changes in green stand out more than changes in blue.
+    /// let gray = ColorRGB::new(100, 100, 100);
+    /// assert!(gray.distance_sq(ColorRGB::new(100, 110, 100)) > gray.distance_sq(ColorRGB::new(100, 100, 110)));
+    /// ```
+    #[inline]
+    pub fn distance_sq(self, other: ColorRGB) -> u32 {
+        let rmean: i32 = (i32::from(self.r) + i32::from(other.r)) / 2;
+        let dr: i32 = i32::from(self.r) - i32::from(other.r);
+        let dg: i32 = i32::from(self.g) - i32::from(other.g);
+        let db: i32 = i32::from(self.b) - i32::from(other.b);
+        let dist: i32 =
+            (((512 + rmean) * dr * dr) >> 8) + 4 * dg * dg + (((767 - rmean) * db * db) >> 8);
+        dist as u32
+    }
 }
 
 impl fmt::Display for ColorRGB {
@@ -196,6 +416,12 @@ impl From<HSV> for ColorRGB {
     }
 }
 
+impl From<HSV16> for ColorRGB {
+    fn from(hsv: HSV16) -> Self {
+        hsv.to_rgb_rainbow()
+    }
+}
+
 impl Index<usize> for ColorRGB {
     type Output = u8;
     #[inline(always)]
@@ -608,3 +834,132 @@ impl ColorRGB {
     pub const Yellow: ColorRGB = ColorRGB::from_color_code(Yellow);
     pub const YellowGreen: ColorRGB = ColorRGB::from_color_code(YellowGreen);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::ColorRGB;
+
+    #[test]
+    fn hue_rotate_primaries() {
+        let mut c = ColorRGB::Red;
+        c.hue_rotate(128);
+        assert_eq!(c, ColorRGB::Cyan);
+
+        let mut c = ColorRGB::new(200, 10, 10);
+        c.hue_rotate(0);
+        assert_eq!(c, ColorRGB::new(200, 10, 10));
+
+        let mut c = ColorRGB::Gray;
+        c.hue_rotate(77);
+        assert_eq!(c, ColorRGB::Gray);
+    }
+
+    #[test]
+    fn hue_rotate_preserves_extremes() {
+        for code in (0..0xFF_FFFFu32).step_by(0x1357) {
+            let color = ColorRGB::from_color_code(code);
+            let max = color.r.max(color.g).max(color.b);
+            let min = color.r.min(color.g).min(color.b);
+            for amount in (0..=255).step_by(11) {
+                let mut c = color;
+                c.hue_rotate(amount);
+                assert_eq!(c.r.max(c.g).max(c.b), max);
+                assert_eq!(c.r.min(c.g).min(c.b), min);
+            }
+        }
+    }
+
+    #[test]
+    fn invert_hue_matches_rotation() {
+        for code in (0..0xFF_FFFFu32).step_by(0x2468) {
+            let mut inverted = ColorRGB::from_color_code(code);
+            let mut rotated = inverted;
+            inverted.invert_hue();
+            rotated.hue_rotate(128);
+            for i in 0..3 {
+                assert!((i16::from(inverted[i]) - i16::from(rotated[i])).abs() <= 1);
+            }
+            inverted.invert_hue();
+            assert_eq!(inverted, ColorRGB::from_color_code(code));
+        }
+    }
+
+    #[test]
+    fn saturation() {
+        let mut c = ColorRGB::new(200, 100, 50);
+        c.desaturate(0);
+        assert_eq!(c, ColorRGB::new(200, 100, 50));
+        c.desaturate(255);
+        assert_eq!(c, ColorRGB::new(116, 116, 116));
+
+        let mut c = ColorRGB::new(200, 100, 50);
+        c.grayscale();
+        assert_eq!(c, ColorRGB::new(116, 116, 116));
+
+        let mut c = ColorRGB::new(140, 100, 90);
+        c.saturate(0);
+        assert_eq!(c, ColorRGB::new(140, 100, 90));
+        c.saturate(255);
+        assert_eq!(c, ColorRGB::new(172, 93, 73));
+
+        // Limited so the red component isn't clipped.
+        let mut c = ColorRGB::new(250, 100, 90);
+        c.saturate(255);
+        assert_eq!(c, ColorRGB::new(254, 98, 88));
+    }
+
+    #[test]
+    fn named_colors() {
+        for &(name, color) in crate::color_codes::NAMED_COLORS.iter() {
+            assert_eq!(ColorRGB::from_name(name), Some(color));
+            assert_eq!(color.nearest_named().1, color);
+            assert_eq!(ColorRGB::from_name(color.name().unwrap()), Some(color));
+        }
+        assert_eq!(
+            ColorRGB::from_name("DARKorange"),
+            Some(ColorRGB::DarkOrange)
+        );
+        assert_eq!(ColorRGB::from_name(""), None);
+        assert_eq!(ColorRGB::Fuchsia.name(), Some("Fuchsia"));
+        assert_eq!(ColorRGB::Magenta.name(), Some("Fuchsia"));
+        assert_eq!(
+            ColorRGB::new(1, 2, 3).nearest_named(),
+            ("Black", ColorRGB::Black)
+        );
+        assert_eq!(ColorRGB::new(250, 0, 5).nearest_named().0, "Red");
+        assert_eq!(ColorRGB::new(0, 0, 130).nearest_named().0, "Navy");
+    }
+
+    #[test]
+    fn distances() {
+        let table = [
+            ((0, 0, 0), (255, 0, 0), 162_308),
+            ((0, 0, 0), (0, 255, 0), 260_100),
+            ((0, 0, 0), (0, 0, 255), 194_820),
+            ((255, 0, 0), (0, 0, 255), 324_870),
+            ((100, 150, 200), (110, 140, 190), 899),
+            ((255, 140, 0), (255, 165, 0), 2500),
+        ];
+        for &((r1, g1, b1), (r2, g2, b2), expected) in table.iter() {
+            let (a, b) = (ColorRGB::new(r1, g1, b1), ColorRGB::new(r2, g2, b2));
+            assert_eq!(a.distance_sq(b), expected);
+            assert_eq!(b.distance_sq(a), expected);
+        }
+
+        let palette = [
+            ColorRGB::Black,
+            ColorRGB::Orange,
+            ColorRGB::DarkOrange,
+            ColorRGB::Black,
+        ];
+        assert_eq!(
+            ColorRGB::new(240, 130, 10).nearest_in_palette(&palette),
+            Some(2)
+        );
+        assert_eq!(
+            ColorRGB::new(250, 170, 0).nearest_in_palette(&palette),
+            Some(1)
+        );
+        assert_eq!(ColorRGB::new(5, 5, 5).nearest_in_palette(&palette), Some(0));
+    }
+}
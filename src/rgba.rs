@@ -0,0 +1,263 @@
+//! Contains the structure and associated methods for a RGB Object with an alpha channel.
+
+#[cfg(feature = "no-std")]
+use core::fmt;
+#[cfg(not(feature = "no-std"))]
+use std::fmt;
+
+use crate::math::scale_u8;
+use crate::{ColorRGB, HSL, HSV, HSV16};
+
+/// Object representing a color through red, green, blue, and alpha (opacity) values.
+///
+/// An alpha of `255` is fully opaque, while an alpha of `0` is fully transparent. Colors are
+/// stored with straight (non-premultiplied) alpha, unless converted with `premultiply()`.
+///
+/// Compositing with `over()` uses the same math as `ColorRGB::blend()`, with the alpha as the
+/// amount of the color to blend in. This means an alpha of `255` results in exactly the
+/// color, and an alpha of `0` leaves the color beneath untouched.
+///
+/// `ColorRGBA` can be created from `ColorRGB`, `HSV`, `HSV16`, and `HSL` (as fully opaque
+/// colors), so the gradient and rainbow traits can fill iterators over `ColorRGBA` too.
+///
+/// # Examples
+///
+/// ```
+/// use cichlid::{ColorRGB, ColorRGBA};
+///
+/// let sprite = ColorRGBA::new(255, 0, 0, 128);
+/// let background = ColorRGB::Blue;
+/// assert_eq!(sprite.over(background), ColorRGB::new(128, 0, 127));
+///
+/// assert_eq!(ColorRGBA::from(ColorRGB::Green).over(background), ColorRGB::Green);
+/// assert_eq!(ColorRGBA::TRANSPARENT.over(background), background);
+/// ```
+///
+/// Filling with a gradient:
+///
+/// ```
+/// use cichlid::{prelude::*, ColorRGB, ColorRGBA};
+///
+/// let mut sprite = [ColorRGBA::TRANSPARENT; 10];
+/// sprite.gradient_fill_rgb_to_inclusive(ColorRGB::Red, ColorRGB::Blue);
+/// assert_eq!(sprite[9], ColorRGBA::new(0, 0, 255, 255));
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub struct ColorRGBA {
+    /// Red Component.
+    pub r: u8,
+    /// Green Component.
+    pub g: u8,
+    /// Blue Component.
+    pub b: u8,
+    /// Alpha Component.
+    pub a: u8,
+}
+
+impl ColorRGBA {
+    /// A fully transparent black.
+    pub const TRANSPARENT: ColorRGBA = ColorRGBA::new(0, 0, 0, 0);
+
+    /// Creates a new `ColorRGBA` object.
+    #[inline(always)]
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        ColorRGBA { r, g, b, a }
+    }
+
+    /// Creates a `ColorRGBA` from a `ColorRGB` and an alpha.
+    #[inline(always)]
+    pub const fn from_rgb(rgb: ColorRGB, a: u8) -> Self {
+        ColorRGBA {
+            r: rgb.r,
+            g: rgb.g,
+            b: rgb.b,
+            a,
+        }
+    }
+
+    /// Returns the color without its alpha.
+    #[inline(always)]
+    pub const fn rgb(self) -> ColorRGB {
+        ColorRGB::new(self.r, self.g, self.b)
+    }
+
+    /// Composites this color over `base`, treating it as a straight alpha color.
+    ///
+    /// This is the same as `base.blend(self.rgb(), self.a)`.
+    #[inline]
+    pub fn over(self, base: ColorRGB) -> ColorRGB {
+        let mut out = base;
+        out.blend(self.rgb(), self.a);
+        out
+    }
+
+    /// Composites this color over `base`, treating it as a premultiplied alpha color.
+    ///
+    /// For a straight alpha color `c`, `c.premultiply().over_premultiplied(base)` is within
+    /// one of `c.over(base)` for each component, and exactly the same when the alpha is `0`
+    /// or `255`.
+    #[inline]
+    pub fn over_premultiplied(self, base: ColorRGB) -> ColorRGB {
+        let keep: u8 = 255 - self.a;
+        ColorRGB::new(
+            scale_u8(base.r, keep).saturating_add(self.r),
+            scale_u8(base.g, keep).saturating_add(self.g),
+            scale_u8(base.b, keep).saturating_add(self.b),
+        )
+    }
+
+    /// Converts a straight alpha color into a premultiplied alpha color.
+    ///
+    /// Each component is scaled by the alpha the same way `ColorRGB::blend()` scales the
+    /// color being blended in.
+    #[inline]
+    pub fn premultiply(self) -> Self {
+        ColorRGBA::new(
+            scale_u8(self.r, self.a),
+            scale_u8(self.g, self.a),
+            scale_u8(self.b, self.a),
+            self.a,
+        )
+    }
+
+    /// Converts a premultiplied alpha color into a straight alpha color.
+    ///
+    /// Precision is lost for low alpha values, but premultiplying the result always gives
+    /// back the original premultiplied color. Fully transparent colors become
+    /// `ColorRGBA::TRANSPARENT`.
+    #[inline]
+    pub fn unpremultiply(self) -> Self {
+        if self.a == 0 {
+            return ColorRGBA::TRANSPARENT;
+        }
+        let a: u16 = u16::from(self.a);
+        let unscale = |c: u8| ((((u16::from(c) << 8) + a) / (a + 1)).min(255)) as u8;
+        ColorRGBA::new(unscale(self.r), unscale(self.g), unscale(self.b), self.a)
+    }
+}
+
+impl fmt::Display for ColorRGBA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "(r: {}, g: {}, b: {}, a: {})",
+            self.r, self.g, self.b, self.a
+        )
+    }
+}
+
+impl From<ColorRGB> for ColorRGBA {
+    #[inline(always)]
+    fn from(rgb: ColorRGB) -> Self {
+        ColorRGBA::from_rgb(rgb, 255)
+    }
+}
+
+impl From<HSV> for ColorRGBA {
+    #[inline]
+    fn from(hsv: HSV) -> Self {
+        ColorRGBA::from(ColorRGB::from(hsv))
+    }
+}
+
+impl From<HSL> for ColorRGBA {
+    #[inline]
+    fn from(hsl: HSL) -> Self {
+        ColorRGBA::from(ColorRGB::from(hsl))
+    }
+}
+
+impl From<HSV16> for ColorRGBA {
+    #[inline]
+    fn from(hsv: HSV16) -> Self {
+        ColorRGBA::from(ColorRGB::from(hsv))
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for ColorRGBA {
+    #[inline(always)]
+    fn from(other: (u8, u8, u8, u8)) -> Self {
+        Self::new(other.0, other.1, other.2, other.3)
+    }
+}
+
+impl From<[u8; 4]> for ColorRGBA {
+    #[inline(always)]
+    fn from(other: [u8; 4]) -> Self {
+        Self::new(other[0], other[1], other[2], other[3])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::{ColorRGB, ColorRGBA, GradientDirection, HSV};
+
+    #[test]
+    fn over_alpha_extremes() {
+        let base = ColorRGB::new(12, 240, 99);
+        for code in (0..0xFF_FFFFu32).step_by(0x3571) {
+            let rgb = ColorRGB::from_color_code(code);
+            assert_eq!(ColorRGBA::from_rgb(rgb, 255).over(base), rgb);
+            assert_eq!(ColorRGBA::from_rgb(rgb, 0).over(base), base);
+            assert_eq!(
+                ColorRGBA::from_rgb(rgb, 255)
+                    .premultiply()
+                    .over_premultiplied(base),
+                rgb
+            );
+            assert_eq!(
+                ColorRGBA::from_rgb(rgb, 0)
+                    .premultiply()
+                    .over_premultiplied(base),
+                base
+            );
+        }
+    }
+
+    #[test]
+    fn premultiplied_matches_straight() {
+        let base = ColorRGB::new(200, 7, 128);
+        for a in 0..=255u8 {
+            for c in (0..=255u8).step_by(3) {
+                let color = ColorRGBA::new(c, 255 - c, c / 2, a);
+                let straight = color.over(base);
+                let premul = color.premultiply().over_premultiplied(base);
+                for i in 0..3 {
+                    let diff = i16::from(straight[i]) - i16::from(premul[i]);
+                    assert!(diff.abs() <= 1, "{} over {}", color, base);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unpremultiply_round_trip() {
+        for a in 0..=255u8 {
+            for c in 0..=255u8 {
+                let premul = ColorRGBA::new(c, c, c, a).premultiply();
+                assert_eq!(premul.unpremultiply().premultiply(), premul);
+            }
+        }
+        let opaque = ColorRGBA::new(1, 2, 3, 255);
+        assert_eq!(opaque.premultiply(), opaque);
+        assert_eq!(opaque.unpremultiply(), opaque);
+    }
+
+    #[test]
+    fn fills() {
+        let mut colors = [ColorRGBA::TRANSPARENT; 20];
+        colors.rainbow_fill_single_cycle(0);
+        assert_eq!(
+            colors[0],
+            ColorRGBA::from(ColorRGB::from(HSV::new(0, 255, 255)))
+        );
+
+        colors.gradient_fill_to_inclusive(
+            HSV::new(0, 255, 255),
+            HSV::new(100, 255, 255),
+            GradientDirection::Forward,
+        );
+        assert!(colors.iter().all(|c| c.a == 255));
+    }
+}
@@ -0,0 +1,405 @@
+//! Logical segments of a physical strip.
+//!
+//! A [`Segment`] is a view over part of a strip of `ColorRGB`s, which can run in reverse,
+//! start from an offset, or mirror one half of itself onto the other. Segments can be iterated
+//! over mutably, so every fill and gradient trait in the [`prelude`] works on them directly.
+//!
+//! A mirrored segment is only filled over its first half. Call [`Segment::mirror()`] after
+//! filling it to copy the first half onto the second.
+//!
+//! # Examples
+//!
+//! ```
+//! use cichlid::{prelude::*, ColorRGB, GradientDirection, HSV};
+//! use cichlid::segment::Segment;
+//!
+//! let mut leds = [ColorRGB::Black; 20];
+//! {
+//!     // The second half of the strip is mounted backwards.
+//!     let (left, right) = leds.split_at_mut(10);
+//!     let mut left = Segment::new(left, 0, 10);
+//!     let mut right = Segment::new(right, 0, 10).with_reversed(true);
+//!
+//!     let start = HSV::new(0, 255, 255);
+//!     let end = HSV::new(96, 255, 255);
+//!     left.gradient_fill_to_inclusive(start, end, GradientDirection::Forward);
+//!     right.gradient_fill_to_inclusive(start, end, GradientDirection::Forward);
+//! }
+//! assert_eq!(leds[0], leds[19]);
+//! assert_eq!(leds[9], leds[10]);
+//! ```
+//!
+//! [`Segment`]: ./struct.Segment.html
+//! [`Segment::mirror()`]: ./struct.Segment.html#method.mirror
+//! [`prelude`]: ../prelude/index.html
+
+#[cfg(feature = "no-std")]
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+#[cfg(not(feature = "no-std"))]
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+
+#[cfg(feature = "no-std")]
+use core::slice;
+#[cfg(not(feature = "no-std"))]
+use std::slice;
+
+use crate::ColorRGB;
+
+/// A view over a run of pixels in a strip, with an optional direction, offset, and mirroring.
+///
+/// Pixels of the view are addressed by their logical index, from `0` to `len()`:
+///
+/// - A view with an offset starts at pixel `offset` of the view and wraps back around to the
+///   start.
+/// - A reversed view runs from the last pixel of the segment to the first.
+/// - A mirrored view only covers the first half of the segment. The first half is copied onto
+///   the second half in reverse when `mirror()` is called, so call it once the view has been
+///   filled.
+///
+/// See the [module level documentation](./index.html) for more.
+#[derive(Debug)]
+pub struct Segment<'a> {
+    pixels: &'a mut [ColorRGB],
+    reversed: bool,
+    mirrored: bool,
+    offset: usize,
+}
+
+impl<'a> Segment<'a> {
+    /// Creates a new `Segment` over the `len` pixels of `pixels` starting at `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start + len` is past the end of `pixels`.
+    pub fn new(pixels: &'a mut [ColorRGB], start: usize, len: usize) -> Self {
+        assert!(
+            matches!(start.checked_add(len), Some(end) if end <= pixels.len()),
+            "segment is out of bounds"
+        );
+        Segment {
+            pixels: &mut pixels[start..start + len],
+            reversed: false,
+            mirrored: false,
+            offset: 0,
+        }
+    }
+
+    /// Sets whether the view runs in reverse.
+    #[inline]
+    pub fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Sets whether the second half of the segment mirrors the first half.
+    #[inline]
+    pub fn with_mirrored(mut self, mirrored: bool) -> Self {
+        self.mirrored = mirrored;
+        self
+    }
+
+    /// Sets the pixel the view starts from, wrapping around the length of the view.
+    #[inline]
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns `true` if the view runs in reverse.
+    #[inline(always)]
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Returns `true` if the second half of the segment mirrors the first half.
+    #[inline(always)]
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    /// Returns the offset the view starts from.
+    #[inline(always)]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of pixels in the view.
+    ///
+    /// For a mirrored segment, this is the length of the first half, including the middle
+    /// pixel of an odd length segment.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.mirrored {
+            true => self.pixels.len() - self.pixels.len() / 2,
+            false => self.pixels.len(),
+        }
+    }
+
+    /// Returns `true` if the view has no pixels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    /// Returns every pixel of the segment, in strip order.
+    #[inline(always)]
+    pub fn pixels(&self) -> &[ColorRGB] {
+        self.pixels
+    }
+
+    /// Returns every pixel of the segment mutably, in strip order.
+    #[inline(always)]
+    pub fn pixels_mut(&mut self) -> &mut [ColorRGB] {
+        self.pixels
+    }
+
+    /// Returns the position in the segment of the pixel at logical `index`, or `None` if
+    /// the index is out of bounds.
+    pub fn index(&self, index: usize) -> Option<usize> {
+        let len: usize = self.len();
+        if index >= len {
+            return None;
+        }
+        let rotated: usize = (index + self.offset % len) % len;
+        match self.reversed {
+            true => Some(len - 1 - rotated),
+            false => Some(rotated),
+        }
+    }
+
+    /// Returns the color of the pixel at logical `index`, or `None` if the index is out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<ColorRGB> {
+        self.index(index).map(|i| self.pixels[i])
+    }
+
+    /// Returns a mutable reference to the pixel at logical `index`, or `None` if the index is
+    /// out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut ColorRGB> {
+        let i: usize = self.index(index)?;
+        Some(&mut self.pixels[i])
+    }
+
+    /// Sets the pixel at logical `index` to a `color`. Indices out of bounds are ignored.
+    #[inline]
+    pub fn set(&mut self, index: usize, color: ColorRGB) {
+        if let Some(pixel) = self.get_mut(index) {
+            *pixel = color;
+        }
+    }
+
+    /// Returns an iterator over each pixel of the view, in logical order.
+    pub fn iter_mut(&mut self) -> SegmentIterMut<'_> {
+        let len: usize = self.len();
+        let offset: usize = match len {
+            0 => 0,
+            _ => self.offset % len,
+        };
+        let (view, _) = self.pixels.split_at_mut(len);
+        // Logical order is `[offset..]` then `[..offset]`, or both halves backwards from
+        // `len - offset` when reversed.
+        let split: usize = match self.reversed {
+            true => len - offset,
+            false => offset,
+        };
+        let (head, tail) = view.split_at_mut(split);
+        match self.reversed {
+            true => SegmentIterMut {
+                first: head.iter_mut(),
+                second: tail.iter_mut(),
+                reversed: true,
+            },
+            false => SegmentIterMut {
+                first: tail.iter_mut(),
+                second: head.iter_mut(),
+                reversed: false,
+            },
+        }
+    }
+
+    /// Copies the first half of a mirrored segment onto the second half, in reverse.
+    ///
+    /// Changes made through the view only reach the second half once this is called. This
+    /// does nothing if the segment isn't mirrored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cichlid::{prelude::*, ColorRGB};
+    /// use cichlid::segment::Segment;
+    ///
+    /// let mut leds = [ColorRGB::Black; 6];
+    /// let mut segment = Segment::new(&mut leds, 0, 6).with_mirrored(true);
+    /// segment.gradient_fill_rgb(ColorRGB::Red, ColorRGB::Blue);
+    /// assert_eq!(segment.pixels()[5], ColorRGB::Black);
+    ///
+    /// segment.mirror();
+    /// assert_eq!(segment.pixels()[5], ColorRGB::Red);
+    /// ```
+    pub fn mirror(&mut self) {
+        if self.mirrored {
+            let len: usize = self.pixels.len();
+            for i in 0..len / 2 {
+                self.pixels[len - 1 - i] = self.pixels[i];
+            }
+        }
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s mut Segment<'a> {
+    type Item = &'s mut ColorRGB;
+    type IntoIter = SegmentIterMut<'s>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over the pixels of a `Segment`, in logical order.
+///
+/// Created by `Segment::iter_mut()`.
+#[derive(Debug)]
+pub struct SegmentIterMut<'s> {
+    first: slice::IterMut<'s, ColorRGB>,
+    second: slice::IterMut<'s, ColorRGB>,
+    reversed: bool,
+}
+
+impl<'s> Iterator for SegmentIterMut<'s> {
+    type Item = &'s mut ColorRGB;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reversed {
+            true => self.first.next_back().or_else(|| self.second.next_back()),
+            false => self.first.next().or_else(|| self.second.next()),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'s> DoubleEndedIterator for SegmentIterMut<'s> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.reversed {
+            true => self.second.next().or_else(|| self.first.next()),
+            false => self.second.next_back().or_else(|| self.first.next_back()),
+        }
+    }
+}
+
+impl<'s> ExactSizeIterator for SegmentIterMut<'s> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+}
+
+impl<'s> FusedIterator for SegmentIterMut<'s> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    fn numbered() -> [ColorRGB; 10] {
+        let mut leds = [ColorRGB::Black; 10];
+        leds.iter_mut()
+            .enumerate()
+            .for_each(|(i, c)| *c = ColorRGB::new(i as u8, 0, 0));
+        leds
+    }
+
+    fn order(segment: &mut Segment) -> ([u8; 10], usize) {
+        let mut out = [0; 10];
+        let mut n = 0;
+        for (o, c) in out.iter_mut().zip(segment.iter_mut()) {
+            *o = c.r;
+            n += 1;
+        }
+        (out, n)
+    }
+
+    #[test]
+    fn orders() {
+        let mut leds = numbered();
+        let mut segment = Segment::new(&mut leds, 2, 6);
+        assert_eq!(order(&mut segment), ([2, 3, 4, 5, 6, 7, 0, 0, 0, 0], 6));
+
+        let mut segment = segment.with_offset(8);
+        assert_eq!(order(&mut segment), ([4, 5, 6, 7, 2, 3, 0, 0, 0, 0], 6));
+
+        let mut segment = segment.with_offset(0).with_reversed(true);
+        assert_eq!(order(&mut segment), ([7, 6, 5, 4, 3, 2, 0, 0, 0, 0], 6));
+
+        let mut segment = segment.with_offset(2);
+        assert_eq!(order(&mut segment), ([5, 4, 3, 2, 7, 6, 0, 0, 0, 0], 6));
+        for i in 0..6 {
+            assert_eq!(segment.get(i).map(|c| c.r), Some(order(&mut segment).0[i]));
+        }
+        assert_eq!(segment.get(6), None);
+    }
+
+    #[test]
+    fn double_ended() {
+        let mut leds = numbered();
+        let mut segment = Segment::new(&mut leds, 0, 10)
+            .with_reversed(true)
+            .with_offset(3);
+        let mut iter = segment.iter_mut();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next_back().map(|c| c.r), Some(7));
+        assert_eq!(iter.next().map(|c| c.r), Some(6));
+        assert_eq!(iter.len(), 8);
+        let rest: usize = iter.rev().map(|c| c.r as usize).sum();
+        assert_eq!(rest, 45 - 7 - 6);
+    }
+
+    #[test]
+    fn mirrored() {
+        let mut leds = [ColorRGB::Black; 9];
+        {
+            let mut segment = Segment::new(&mut leds, 0, 9).with_mirrored(true);
+            assert_eq!(segment.len(), 5);
+            segment.rainbow_fill(0, 10 << 8);
+            assert_eq!(segment.pixels()[8], ColorRGB::Black);
+            segment.mirror();
+        }
+        for i in 0..9 {
+            assert_eq!(leds[i], leds[8 - i]);
+        }
+        assert_eq!(leds[4], ColorRGB::from(crate::HSV::new(40, 255, 255)));
+    }
+
+    #[test]
+    fn mirrored_gradient() {
+        let mut leds = [ColorRGB::Black; 8];
+        let mut segment = Segment::new(&mut leds, 0, 8).with_mirrored(true);
+        segment.gradient_fill_rgb_to_inclusive(ColorRGB::Red, ColorRGB::Blue);
+        // Until mirrored, the second half is untouched.
+        assert!(segment.pixels()[4..].iter().all(|c| *c == ColorRGB::Black));
+
+        segment.mirror();
+        assert_eq!(segment.pixels()[3], ColorRGB::Blue);
+        assert_eq!(segment.pixels()[4], ColorRGB::Blue);
+        assert_eq!(segment.pixels()[7], ColorRGB::Red);
+        for i in 0..4 {
+            assert_eq!(segment.pixels()[i], segment.pixels()[7 - i]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let mut leds = numbered();
+        Segment::new(&mut leds, 5, 6);
+    }
+}
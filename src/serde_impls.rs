@@ -0,0 +1,133 @@
+//! `serde` implementations for colors.
+//!
+//! `ColorRGB` serializes as a `"#RRGGBB"` string in human readable formats such as JSON and
+//! TOML, and as a tuple of three bytes in binary formats. Any string accepted by
+//! `ColorRGB::from_str()` can be deserialized, such as `"rgb(10, 20, 30)"` or `"DarkOrange"`.
+//!
+//! `HSV` and `GradientDirection` use derived implementations.
+
+#[cfg(feature = "no-std")]
+use core::{fmt, str};
+#[cfg(not(feature = "no-std"))]
+use std::{fmt, str};
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::ColorRGB;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+impl Serialize for ColorRGB {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (r, g, b) = (self.r, self.g, self.b);
+        if serializer.is_human_readable() {
+            let mut buf: [u8; 7] = [b'#'; 7];
+            for (i, c) in [r, g, b].iter().enumerate() {
+                buf[1 + i * 2] = HEX_DIGITS[usize::from(c >> 4)];
+                buf[2 + i * 2] = HEX_DIGITS[usize::from(c & 0xF)];
+            }
+            serializer.serialize_str(str::from_utf8(&buf).map_err(serde::ser::Error::custom)?)
+        } else {
+            (r, g, b).serialize(serializer)
+        }
+    }
+}
+
+/// Deserializes a `ColorRGB` from either a string or a sequence of bytes.
+struct ColorRGBVisitor;
+
+impl<'de> Visitor<'de> for ColorRGBVisitor {
+    type Value = ColorRGB;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a \"#RRGGBB\" color or three bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<ColorRGB, E> {
+        v.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ColorRGB, A::Error> {
+        let mut next = |i: usize| -> Result<u8, A::Error> {
+            seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))
+        };
+        Ok(ColorRGB::new(next(0)?, next(1)?, next(2)?))
+    }
+}
+
+impl<'de> Deserialize<'de> for ColorRGB {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ColorRGBVisitor)
+        } else {
+            deserializer.deserialize_tuple(3, ColorRGBVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ColorRGB, GradientDirection, HSV};
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    #[test]
+    fn color_rgb() {
+        let color = ColorRGB::new(0x12, 0xAB, 0x00);
+        assert_tokens(&color.readable(), &[Token::Str("#12AB00")]);
+        assert_tokens(
+            &color.compact(),
+            &[
+                Token::Tuple { len: 3 },
+                Token::U8(0x12),
+                Token::U8(0xAB),
+                Token::U8(0x00),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(&ColorRGB::White.readable(), &[Token::Str("#FFFFFF")]);
+        serde_test::assert_de_tokens(&color.readable(), &[Token::Str("#12ab00")]);
+        serde_test::assert_de_tokens(&ColorRGB::Red.readable(), &[Token::Str("red")]);
+    }
+
+    #[test]
+    fn color_rgb_errors() {
+        assert_de_tokens_error::<serde_test::Readable<ColorRGB>>(
+            &[Token::Str("12AB00")],
+            "invalid value: string \"12AB00\", expected a \"#RRGGBB\" color or three bytes",
+        );
+        assert_de_tokens_error::<serde_test::Readable<ColorRGB>>(
+            &[Token::Str("#12AB0G")],
+            "invalid value: string \"#12AB0G\", expected a \"#RRGGBB\" color or three bytes",
+        );
+    }
+
+    #[test]
+    fn hsv_and_directions() {
+        assert_tokens(
+            &HSV::new(1, 2, 3),
+            &[
+                Token::Struct {
+                    name: "HSV",
+                    len: 3,
+                },
+                Token::Str("h"),
+                Token::U8(1),
+                Token::Str("s"),
+                Token::U8(2),
+                Token::Str("v"),
+                Token::U8(3),
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &GradientDirection::Longest,
+            &[Token::UnitVariant {
+                name: "GradientDirection",
+                variant: "Longest",
+            }],
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Interoperability with the `smart-leds` ecosystem.
+//!
+//! Driver crates implementing `smart_leds::SmartLedsWrite` take colors as `RGB8`, which
+//! `smart-leds` re-exports from the `rgb` crate. This module provides conversions between
+//! `ColorRGB` and the `rgb` types, casts between slices of each without copying, and an
+//! [`Output`] iterator that adjusts each color as it's written out to the LEDs.
+//!
+//! # Examples
+//!
+//! ```
+//! use cichlid::{prelude::*, ColorRGB};
+//! use cichlid::smart_leds::{as_rgb8, Output, RGB8};
+//!
+//! let mut leds = [ColorRGB::Black; 30];
+//! leds.rainbow_fill_single_cycle(0);
+//!
+//! // Can be passed to `SmartLedsWrite::write()` directly.
+//! let colors: &[RGB8] = as_rgb8(&leds);
+//! assert_eq!(colors[0], RGB8::new(255, 0, 0));
+//!
+//! // Or adjusted on the fly, at half brightness.
+//! let mut output = Output::new(&leds, |c| c).with_brightness(128);
+//! assert_eq!(output.next(), Some(RGB8::new(128, 0, 0)));
+//! ```
+//!
+//! [`Output`]: ./struct.Output.html
+
+#[cfg(feature = "no-std")]
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+#[cfg(not(feature = "no-std"))]
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+
+#[cfg(feature = "no-std")]
+use core::{mem, slice};
+#[cfg(not(feature = "no-std"))]
+use std::{mem, slice};
+
+pub use rgb::RGB8;
+use rgb::RGBA;
+
+use crate::ColorRGB;
+
+// `ColorRGB` and `RGB8` must have the exact same layout for the slice casts below.
+const _: () = assert!(mem::size_of::<ColorRGB>() == mem::size_of::<RGB8>());
+const _: () = assert!(mem::align_of::<ColorRGB>() == mem::align_of::<RGB8>());
+
+impl From<RGB8> for ColorRGB {
+    #[inline(always)]
+    fn from(color: RGB8) -> Self {
+        ColorRGB::new(color.r, color.g, color.b)
+    }
+}
+
+impl From<ColorRGB> for RGB8 {
+    #[inline(always)]
+    fn from(color: ColorRGB) -> Self {
+        RGB8::new(color.r, color.g, color.b)
+    }
+}
+
+impl<W> From<RGBA<u8, W>> for ColorRGB {
+    /// Converts from an RGBW color, such as `smart_leds::RGBW<u8>`, ignoring the white
+    /// channel.
+    #[inline(always)]
+    fn from(color: RGBA<u8, W>) -> Self {
+        ColorRGB::new(color.r, color.g, color.b)
+    }
+}
+
+impl<W: Default> From<ColorRGB> for RGBA<u8, W> {
+    /// Converts to an RGBW color, such as `smart_leds::RGBW<u8>`, with the white channel
+    /// left at its default (off).
+    #[inline(always)]
+    fn from(color: ColorRGB) -> Self {
+        RGBA {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: W::default(),
+        }
+    }
+}
+
+/// Casts a slice of `ColorRGB`s to a slice of `RGB8`s, without copying.
+#[inline(always)]
+pub fn as_rgb8(colors: &[ColorRGB]) -> &[RGB8] {
+    // Safety: Both types are three `u8`s in red, green, blue order, with an alignment of 1.
+    unsafe { slice::from_raw_parts(colors.as_ptr() as *const RGB8, colors.len()) }
+}
+
+/// Casts a mutable slice of `ColorRGB`s to a mutable slice of `RGB8`s, without copying.
+#[inline(always)]
+pub fn as_rgb8_mut(colors: &mut [ColorRGB]) -> &mut [RGB8] {
+    // Safety: See `as_rgb8()`.
+    unsafe { slice::from_raw_parts_mut(colors.as_mut_ptr() as *mut RGB8, colors.len()) }
+}
+
+/// Casts a slice of `RGB8`s to a slice of `ColorRGB`s, without copying.
+#[inline(always)]
+pub fn from_rgb8(colors: &[RGB8]) -> &[ColorRGB] {
+    // Safety: See `as_rgb8()`.
+    unsafe { slice::from_raw_parts(colors.as_ptr() as *const ColorRGB, colors.len()) }
+}
+
+/// Casts a mutable slice of `RGB8`s to a mutable slice of `ColorRGB`s, without copying.
+#[inline(always)]
+pub fn from_rgb8_mut(colors: &mut [RGB8]) -> &mut [ColorRGB] {
+    // Safety: See `as_rgb8()`.
+    unsafe { slice::from_raw_parts_mut(colors.as_mut_ptr() as *mut ColorRGB, colors.len()) }
+}
+
+/// Iterator converting each `ColorRGB` of a strip into an `RGB8` as it's written out, passing
+/// it through a `pipeline` and brightness first.
+///
+/// This leaves the strip itself untouched, so effects can keep building on the unadjusted
+/// colors from frame to frame.
+///
+/// See the [module level documentation](./index.html) for more.
+#[derive(Clone, Debug)]
+pub struct Output<'a, F> {
+    pixels: slice::Iter<'a, ColorRGB>,
+    pipeline: F,
+    brightness: u8,
+}
+
+impl<'a, F> Output<'a, F>
+where
+    F: FnMut(ColorRGB) -> ColorRGB,
+{
+    /// Creates a new `Output` over `pixels`, with each color passed through `pipeline`.
+    #[inline]
+    pub fn new(pixels: &'a [ColorRGB], pipeline: F) -> Self {
+        Output {
+            pixels: pixels.iter(),
+            pipeline,
+            brightness: 255,
+        }
+    }
+
+    /// Scales each color by `brightness` after the pipeline, using `ColorRGB::scale()`.
+    #[inline]
+    pub fn with_brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    #[inline(always)]
+    fn output(&mut self, color: ColorRGB) -> RGB8 {
+        let mut color: ColorRGB = (self.pipeline)(color);
+        if self.brightness != 255 {
+            color.scale(self.brightness);
+        }
+        RGB8::from(color)
+    }
+}
+
+impl<'a, F> Iterator for Output<'a, F>
+where
+    F: FnMut(ColorRGB) -> ColorRGB,
+{
+    type Item = RGB8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let color: ColorRGB = *self.pixels.next()?;
+        Some(self.output(color))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pixels.size_hint()
+    }
+}
+
+impl<'a, F> DoubleEndedIterator for Output<'a, F>
+where
+    F: FnMut(ColorRGB) -> ColorRGB,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let color: ColorRGB = *self.pixels.next_back()?;
+        Some(self.output(color))
+    }
+}
+
+impl<'a, F> ExactSizeIterator for Output<'a, F> where F: FnMut(ColorRGB) -> ColorRGB {}
+
+impl<'a, F> FusedIterator for Output<'a, F> where F: FnMut(ColorRGB) -> ColorRGB {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let color = ColorRGB::new(1, 2, 3);
+        assert_eq!(RGB8::from(color), RGB8::new(1, 2, 3));
+        assert_eq!(ColorRGB::from(RGB8::new(1, 2, 3)), color);
+
+        let rgbw: RGBA<u8, u8> = color.into();
+        assert_eq!(rgbw, RGBA::new(1, 2, 3, 0));
+        assert_eq!(ColorRGB::from(RGBA::new(1, 2, 3, 200)), color);
+    }
+
+    #[test]
+    fn slice_casts() {
+        let mut leds = [ColorRGB::new(1, 2, 3), ColorRGB::new(4, 5, 6)];
+        assert_eq!(as_rgb8(&leds), &[RGB8::new(1, 2, 3), RGB8::new(4, 5, 6)]);
+        as_rgb8_mut(&mut leds)[1].g = 50;
+        assert_eq!(leds[1], ColorRGB::new(4, 50, 6));
+
+        let mut colors = [RGB8::new(7, 8, 9)];
+        from_rgb8_mut(&mut colors)[0].r = 70;
+        assert_eq!(from_rgb8(&colors), &[ColorRGB::new(70, 8, 9)]);
+    }
+
+    #[test]
+    fn output() {
+        let leds = [ColorRGB::White, ColorRGB::Red, ColorRGB::Blue];
+        let mut output = Output::new(&leds, |mut c: ColorRGB| {
+            c.g = 0;
+            c
+        })
+        .with_brightness(64);
+        assert_eq!(output.len(), 3);
+        assert_eq!(output.next_back(), Some(RGB8::new(0, 0, 64)));
+        assert_eq!(output.next(), Some(RGB8::new(64, 0, 64)));
+        assert_eq!(output.next(), Some(RGB8::new(64, 0, 0)));
+        assert_eq!(output.next(), None);
+        assert_eq!(leds[0], ColorRGB::White);
+    }
+}